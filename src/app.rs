@@ -1,24 +1,39 @@
+// only graphics is built without the web feature because the interpreter uses its colors
+#[cfg(feature = "web")]
 pub(crate) mod dom;
 pub(crate) mod graphics;
+#[cfg(feature = "web")]
+pub(crate) mod keyboard;
+#[cfg(feature = "web")]
 pub(crate) mod lens;
+#[cfg(feature = "web")]
 pub(crate) mod render_object;
+#[cfg(feature = "web")]
+pub(crate) mod theme;
+#[cfg(feature = "web")]
 pub(crate) mod vdom;
 
+#[cfg(feature = "web")]
 #[macro_use]
 pub(crate) mod widgets;
 
+#[cfg(feature = "web")]
 use std::{cell::RefCell, rc::Rc};
 
+#[cfg(feature = "web")]
 use wasm_bindgen::JsValue;
 
+#[cfg(feature = "web")]
 use crate::app::widgets::Widget;
 
+#[cfg(feature = "web")]
 struct App<Data, DataAsWidget: Widget<Data>, ToWidget: Fn(&Data) -> DataAsWidget> {
     data: Data,
     dom: dom::Dom,
     to_widget: ToWidget,
 }
 
+#[cfg(feature = "web")]
 impl<Data: 'static, DataAsWidget: Widget<Data> + 'static, ToWidget: Fn(&Data) -> DataAsWidget + Copy + 'static> App<Data, DataAsWidget, ToWidget> {
     fn run_update(app_refcell: &Rc<RefCell<Self>>, event: JsValue, closure: &dyn Fn(JsValue, &mut Data)) {
         closure(event, &mut app_refcell.borrow_mut().data);
@@ -34,6 +49,7 @@ impl<Data: 'static, DataAsWidget: Widget<Data> + 'static, ToWidget: Fn(&Data) ->
     }
 }
 
+#[cfg(feature = "web")]
 pub(crate) fn run<Data: 'static, DataAsWidget: Widget<Data> + 'static>(data: Data, to_widget: impl Fn(&Data) -> DataAsWidget + Copy + 'static) {
     let document = web_sys::window().expect("no global window").document().expect("no document on window");
     let app_div = document.get_element_by_id("app").expect("no element with id 'app'");
@@ -1,4 +1,8 @@
-use std::collections::HashMap;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
 
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
 use web_sys::js_sys;
@@ -14,10 +18,21 @@ pub(crate) struct Dom {
 struct Element {
     dom_element: web_sys::Element,
     type_: vdom::ElementType,
-    props: HashMap<&'static str, JsValue>,
+    props: HashMap<&'static str, vdom::Prop>,
     event_listeners: Vec<(&'static str, Closure<dyn Fn(JsValue)>)>,
+    timers: Vec<Timer>,
     children: Vec<Node>,
 }
+// cancels the timeout when it is dropped, so that the callback is never called after the element that it belongs to is gone
+struct Timer {
+    handle: i32,
+    delay: u32,
+    // replaced when the element is updated, so that a timer that keeps running calls the callback of the newest vdom
+    callback: Rc<RefCell<Rc<dyn Fn()>>>,
+    // a timeout only goes off once, so a timer that already went off has to be started again
+    fired: Rc<Cell<bool>>,
+    _closure: Closure<dyn Fn()>,
+}
 enum Node {
     Element(Element),
     Text(web_sys::Text, String),
@@ -27,7 +42,7 @@ impl Dom {
     pub(crate) fn new_empty(document: web_sys::Document, parent: &web_sys::Node) -> Dom {
         let dom_element = document.create_element(vdom::ElementType::Div.stringify()).unwrap(); // TODO: handle errors properly
         parent.append_child(&dom_element).unwrap(); // TODO: handle this error properly
-        let elem = Element { dom_element, type_: vdom::ElementType::Div, props: HashMap::new(), event_listeners: Vec::new(), children: Vec::new() };
+        let elem = Element { dom_element, type_: vdom::ElementType::Div, props: HashMap::new(), event_listeners: Vec::new(), timers: Vec::new(), children: Vec::new() };
         Dom { document, root: elem }
     }
 
@@ -40,12 +55,13 @@ impl Element {
         // TODO: handle all the errors properly (all the unwraps)
         let dom_element = document.create_element(vdom.type_.stringify()).unwrap();
 
-        for (prop_name, prop_value) in &vdom.props {
-            js_sys::Reflect::set(&dom_element, &(*prop_name).into(), prop_value).unwrap();
+        for (prop_name, prop_value) in props_in_order(&vdom.props) {
+            set_prop(&dom_element, prop_name, prop_value);
         }
 
         let event_listeners = Self::convert_event_listeners_from_vdom(run_update, vdom.event_listeners);
         Self::add_event_listeners(&dom_element, &event_listeners);
+        let timers = vdom.timers.into_iter().map(|(delay, callback)| Timer::start(delay, Self::timer_callback(run_update, callback))).collect();
 
         let children = vdom
             .children
@@ -58,7 +74,7 @@ impl Element {
             })
             .collect();
 
-        Element { dom_element, type_: vdom.type_, props: vdom.props, event_listeners, children }
+        Element { dom_element, type_: vdom.type_, props: vdom.props, event_listeners, timers, children }
     }
 
     fn update<Data: 'static>(&mut self, document: &web_sys::Document, run_update: &(impl Fn(JsValue, &dyn Fn(JsValue, &mut Data)) + Clone + 'static), new_vdom: vdom::Element<Data>) {
@@ -72,8 +88,7 @@ impl Element {
             // remove old properties
             self.props.retain(|old_prop_name, _| {
                 if !new_vdom.props.contains_key(old_prop_name) {
-                    // TODO: deal with error properly
-                    js_sys::Reflect::delete_property(&self.dom_element, &(*old_prop_name).into()).unwrap();
+                    remove_prop(&self.dom_element, old_prop_name);
                     false
                 } else {
                     true
@@ -81,18 +96,37 @@ impl Element {
             });
 
             // set new properties
-            for (new_prop_name, new_value) in new_vdom.props {
-                // TODO: deal with error properly
+            for (new_prop_name, new_value) in props_in_order(&new_vdom.props) {
                 // TODO: only set if value changed?
-                js_sys::Reflect::set(&self.dom_element, &(*new_prop_name).into(), &new_value).unwrap();
-                self.props.insert(new_prop_name, new_value);
+                set_prop(&self.dom_element, new_prop_name, new_value);
             }
+            self.props.extend(new_vdom.props);
 
             Self::remove_event_listeners(&self.dom_element, &self.event_listeners);
             self.event_listeners.clear();
             self.event_listeners = Self::convert_event_listeners_from_vdom(run_update, new_vdom.event_listeners);
             Self::add_event_listeners(&self.dom_element, &self.event_listeners);
 
+            // timers are only restarted if they changed, otherwise updating the dom more often than the delay would stop them from ever going off
+            let kept = kept_timers(&self.timers.iter().map(|timer| (timer.delay, timer.fired.get())).collect::<Vec<_>>(), &new_vdom.timers.iter().map(|(delay, _)| *delay).collect::<Vec<_>>());
+            let mut old_timers = std::mem::take(&mut self.timers).into_iter();
+            // the old timers that are not kept are cancelled when they are dropped
+            self.timers = new_vdom
+                .timers
+                .into_iter()
+                .zip(kept)
+                .map(|((delay, callback), kept)| {
+                    let callback = Self::timer_callback(run_update, callback);
+                    match old_timers.next() {
+                        Some(timer) if kept => {
+                            *timer.callback.borrow_mut() = callback;
+                            timer
+                        }
+                        _ => Timer::start(delay, callback),
+                    }
+                })
+                .collect();
+
             // update children
             {
                 let child_doms = self.dom_element.child_nodes();
@@ -136,6 +170,11 @@ impl Element {
         }
     }
 
+    fn timer_callback<Data: 'static>(run_update: &(impl Fn(JsValue, &dyn Fn(JsValue, &mut Data)) + Clone + 'static), callback: Box<dyn Fn(&mut Data)>) -> Rc<dyn Fn()> {
+        let run_update = run_update.clone();
+        Rc::new(move || run_update(JsValue::UNDEFINED, &|_, data| callback(data)))
+    }
+
     fn convert_event_listeners_from_vdom<Data: 'static>(
         run_update: &(impl Fn(JsValue, &dyn Fn(JsValue, &mut Data)) + Clone + 'static),
         event_listeners: Vec<(&'static str, Box<dyn Fn(JsValue, &mut Data)>)>,
@@ -187,3 +226,91 @@ impl Node {
         }
     }
 }
+
+impl Timer {
+    fn start(delay: u32, callback: Rc<dyn Fn()>) -> Timer {
+        let callback = Rc::new(RefCell::new(callback));
+        let fired = Rc::new(Cell::new(false));
+        let closure = Closure::<dyn Fn()>::new({
+            let callback = callback.clone();
+            let fired = fired.clone();
+            move || {
+                fired.set(true);
+                // cloned out of the cell because the callback updates the dom, which can replace the callback while it runs
+                let callback = callback.borrow().clone();
+                callback();
+            }
+        });
+        // TODO: handle error correctly instead of unwrapping
+        let handle =
+            web_sys::window().expect("no global window").set_timeout_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), delay.try_into().unwrap_or(i32::MAX)).unwrap();
+        Timer { handle, delay, callback, fired, _closure: closure }
+    }
+}
+
+// which of the new timers can keep the old timer at the same position running, given the delays and whether the old timers already went off
+fn kept_timers(old: &[(u32, bool)], new: &[u32]) -> Vec<bool> {
+    new.iter().enumerate().map(|(index, delay)| old.get(index) == Some(&(*delay, false))).collect()
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            window.clear_timeout_with_handle(self.handle);
+        }
+    }
+}
+
+// aria attributes and roles are set as attributes because not every browser has properties for them
+fn is_attribute(prop_name: &str) -> bool {
+    prop_name == "role" || prop_name.starts_with("aria-")
+}
+
+fn set_prop(dom_element: &web_sys::Element, prop_name: &'static str, prop_value: &vdom::Prop) {
+    // TODO: deal with errors properly
+    if is_attribute(prop_name) {
+        let value = match prop_value {
+            vdom::Prop::String(s) => s.clone(),
+            vdom::Prop::Number(n) => n.to_string(),
+            vdom::Prop::Bool(b) => b.to_string(),
+        };
+        dom_element.set_attribute(prop_name, &value).unwrap();
+    } else {
+        js_sys::Reflect::set(dom_element, &prop_name.into(), &prop_value.to_js()).unwrap();
+    }
+}
+
+fn remove_prop(dom_element: &web_sys::Element, prop_name: &'static str) {
+    // TODO: deal with errors properly
+    if is_attribute(prop_name) {
+        dom_element.remove_attribute(prop_name).unwrap();
+    } else {
+        js_sys::Reflect::delete_property(dom_element, &prop_name.into()).unwrap();
+    }
+}
+
+// the value of an input is clamped to its min and max, so it has to be set after them
+fn props_in_order<'props>(props: &'props HashMap<&'static str, vdom::Prop>) -> Vec<(&'static str, &'props vdom::Prop)> {
+    let mut props: Vec<_> = props.iter().map(|(name, value)| (*name, value)).collect();
+    props.sort_by_key(|(name, _)| *name == "value");
+    props
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::dom::kept_timers;
+
+    #[test]
+    fn timers_keep_running_across_updates() {
+        // while playing, the dom is updated for other reasons (like moving the mouse) before the timer for the next step goes off
+        assert_eq!(kept_timers(&[(500, false)], &[500]), [true]);
+        // once it goes off, the step is taken and the timer for the next step has to be started
+        assert_eq!(kept_timers(&[(500, true)], &[500]), [false]);
+        // changing the speed restarts the timer
+        assert_eq!(kept_timers(&[(500, false)], &[250]), [false]);
+        // stopping and starting playing
+        assert_eq!(kept_timers(&[(500, false)], &[]), Vec::<bool>::new());
+        assert_eq!(kept_timers(&[], &[500]), [false]);
+        assert_eq!(kept_timers(&[(500, false), (100, false)], &[500, 200]), [true, false]);
+    }
+}
@@ -1,19 +1,21 @@
 // TODO: REMOVE this module (these are temporary replacements for the sfml structs while i figure out how everything is supposed to work)
 // graphics utilities
 
+#[cfg(feature = "web")]
 #[derive(Copy, Clone, PartialEq)]
 pub(crate) struct Vector2f {
     pub(crate) x: f32,
     pub(crate) y: f32,
 }
 
+#[cfg(feature = "web")]
 impl Vector2f {
     pub(crate) fn new(x: f32, y: f32) -> Self {
         Self { x, y }
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub(crate) struct Color {
     pub(crate) r: u8,
     pub(crate) g: u8,
@@ -22,20 +24,18 @@ pub(crate) struct Color {
 }
 
 impl Color {
+    #[cfg(feature = "web")]
     pub(crate) fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
         Self { r, g, b, a }
     }
-    pub(crate) fn rgb(r: u8, g: u8, b: u8) -> Self {
+    #[cfg(feature = "web")]
+    pub(crate) const fn rgb(r: u8, g: u8, b: u8) -> Self {
         Self { r, g, b, a: 255 }
     }
 
+    // css wants the alpha from 0 to 1
+    #[cfg(feature = "web")]
     pub(crate) fn to_css_color(self) -> String {
-        format!("rgba({}, {}, {}, {})", self.r, self.g, self.b, self.a)
+        format!("rgba({}, {}, {}, {})", self.r, self.g, self.b, f32::from(self.a) / 255.0)
     }
 }
-
-// this isnt even related to graphics!
-// TODO: REMOVE this too
-pub(crate) enum Key {
-    Space,
-}
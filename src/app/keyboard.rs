@@ -0,0 +1,161 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Key {
+    // any key that types one character, including letters, digits and punctuation
+    Character(char),
+    Space,
+    Enter,
+    Escape,
+    Backspace,
+    Tab,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub(crate) struct Modifiers {
+    pub(crate) ctrl: bool,
+    pub(crate) alt: bool,
+    pub(crate) shift: bool,
+    pub(crate) meta: bool,
+}
+
+// a key together with the modifiers that are held down while it is pressed
+// shift is never part of a chord with a character key because shift already changes which character is typed (shift and / types ?), so they would be two names for the same chord
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct KeyChord {
+    pub(crate) modifiers: Modifiers,
+    pub(crate) key: Key,
+}
+
+const NAMED_KEYS: [(Key, &str); 13] = [
+    (Key::Space, "Space"),
+    (Key::Enter, "Enter"),
+    (Key::Escape, "Escape"),
+    (Key::Backspace, "Backspace"),
+    (Key::Tab, "Tab"),
+    (Key::Left, "Left"),
+    (Key::Right, "Right"),
+    (Key::Up, "Up"),
+    (Key::Down, "Down"),
+    (Key::Home, "Home"),
+    (Key::End, "End"),
+    (Key::PageUp, "PageUp"),
+    (Key::PageDown, "PageDown"),
+];
+
+impl KeyChord {
+    pub(crate) fn new(modifiers: Modifiers, key: Key) -> KeyChord {
+        let modifiers = match key {
+            Key::Character(_) => Modifiers { shift: false, ..modifiers },
+            _ => modifiers,
+        };
+        KeyChord { modifiers, key }
+    }
+
+    pub(crate) fn plain(key: Key) -> KeyChord {
+        KeyChord::new(Modifiers::default(), key)
+    }
+
+    // None for keys that cannot be part of a chord, which includes the modifier keys on their own
+    pub(crate) fn from_event(event: &web_sys::KeyboardEvent) -> Option<KeyChord> {
+        let modifiers = Modifiers { ctrl: event.ctrl_key(), alt: event.alt_key(), shift: event.shift_key(), meta: event.meta_key() };
+        let key = match event.key().as_str() {
+            " " => Key::Space,
+            "ArrowLeft" => Key::Left,
+            "ArrowRight" => Key::Right,
+            "ArrowUp" => Key::Up,
+            "ArrowDown" => Key::Down,
+            name => named_key(name).or_else(|| single_char(name).map(Key::Character))?,
+        };
+        Some(KeyChord::new(modifiers, key))
+    }
+}
+
+fn named_key(name: &str) -> Option<Key> {
+    NAMED_KEYS.iter().find(|(_, key_name)| *key_name == name).map(|(key, _)| *key)
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+// written like "Ctrl+Shift+Left", which is also what FromStr parses
+impl Display for KeyChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (held, name) in [(self.modifiers.ctrl, "Ctrl"), (self.modifiers.alt, "Alt"), (self.modifiers.shift, "Shift"), (self.modifiers.meta, "Meta")] {
+            if held {
+                write!(f, "{name}+")?;
+            }
+        }
+        match self.key {
+            Key::Character(c) => write!(f, "{c}"),
+            key => write!(f, "{}", NAMED_KEYS.iter().find(|(named, _)| *named == key).expect("every key other than characters should have a name").1),
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = Modifiers::default();
+        let mut rest = s;
+        // the key itself can be +, so only the parts before a + that is not the last character are modifiers
+        while let Some((modifier, after)) = rest.split_once('+').filter(|(_, after)| !after.is_empty()) {
+            match modifier {
+                "Ctrl" => modifiers.ctrl = true,
+                "Alt" => modifiers.alt = true,
+                "Shift" => modifiers.shift = true,
+                "Meta" => modifiers.meta = true,
+                _ => return Err(format!("unknown modifier '{modifier}'")),
+            }
+            rest = after;
+        }
+        let key = named_key(rest).or_else(|| single_char(rest).map(Key::Character)).ok_or_else(|| format!("unknown key '{rest}'"))?;
+        Ok(KeyChord::new(modifiers, key))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::keyboard::{Key, KeyChord, Modifiers};
+
+    #[test]
+    fn round_trip() {
+        for chord in [
+            KeyChord::plain(Key::Space),
+            KeyChord::plain(Key::Character('+')),
+            KeyChord::new(Modifiers { ctrl: true, ..Modifiers::default() }, Key::Character('+')),
+            KeyChord::new(Modifiers { ctrl: true, alt: true, shift: true, meta: true }, Key::Left),
+            KeyChord::plain(Key::Character('?')),
+        ] {
+            assert_eq!(chord.to_string().parse::<KeyChord>(), Ok(chord));
+        }
+        assert_eq!(KeyChord::new(Modifiers { ctrl: true, shift: true, ..Modifiers::default() }, Key::PageDown).to_string(), "Ctrl+Shift+PageDown");
+    }
+
+    #[test]
+    fn shift_with_characters() {
+        assert_eq!("Shift+?".parse::<KeyChord>(), Ok(KeyChord::plain(Key::Character('?'))));
+        assert_ne!("Shift+Left".parse::<KeyChord>(), Ok(KeyChord::plain(Key::Left)));
+    }
+
+    #[test]
+    fn bad_chords() {
+        assert!("Hyper+a".parse::<KeyChord>().is_err());
+        assert!("ab".parse::<KeyChord>().is_err());
+        assert!("".parse::<KeyChord>().is_err());
+    }
+}
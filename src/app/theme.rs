@@ -0,0 +1,209 @@
+use crate::app::{graphics::Color, widgets::label::Label};
+
+// the colors and fonts that everything is shown with, which are picked by what they are used for instead of by what they look like
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Theme {
+    pub(crate) name: &'static str,
+
+    pub(crate) background: Color,
+    pub(crate) text: Color,
+    // text that is less important, like the labels of boxes and variables that are shadowed
+    pub(crate) muted: Color,
+    pub(crate) border: Color,
+    pub(crate) badge_background: Color,
+
+    // the code that is about to run
+    pub(crate) primary_highlight: Color,
+    // other parts of the code that are related to what is shown, like the print statement of the output that the mouse is over
+    pub(crate) secondary_highlight: Color,
+    // values that changed in the last step flash with this color
+    pub(crate) changed: Color,
+    pub(crate) error: Color,
+    // the labels of errors, which point to other parts of the code than the error, and links
+    pub(crate) error_label: Color,
+    pub(crate) note: Color,
+    pub(crate) help: Color,
+    // things that are not errors but are probably not what was meant, like variables that are uninitialized
+    pub(crate) warning: Color,
+
+    pub(crate) syntax: SyntaxColors,
+
+    pub(crate) ui_font: &'static str,
+    pub(crate) code_font: &'static str,
+    // in pixels
+    pub(crate) font_size: u32,
+}
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct SyntaxColors {
+    pub(crate) keyword: Color,
+    pub(crate) literal: Color,
+    pub(crate) identifier: Color,
+    pub(crate) operator: Color,
+    pub(crate) comment: Color,
+}
+
+// font zoom is in percent
+pub(crate) const MIN_ZOOM: u32 = 50;
+pub(crate) const MAX_ZOOM: u32 = 300;
+pub(crate) const ZOOM_STEP: u32 = 10;
+
+impl Theme {
+    pub(crate) const LIGHT: Theme = Theme {
+        name: "light",
+        background: Color::rgb(255, 255, 255),
+        text: Color::rgb(20, 20, 20),
+        muted: Color::rgb(120, 120, 120),
+        border: Color::rgb(150, 150, 150),
+        badge_background: Color::rgb(220, 220, 240),
+        primary_highlight: Color::rgb(50, 100, 50),
+        secondary_highlight: Color::rgb(200, 150, 0),
+        changed: Color::rgb(255, 210, 0),
+        error: Color::rgb(150, 0, 0),
+        error_label: Color::rgb(50, 100, 200),
+        note: Color::rgb(50, 100, 50),
+        help: Color::rgb(0, 130, 130),
+        warning: Color::rgb(150, 100, 0),
+        syntax: SyntaxColors {
+            keyword: Color::rgb(150, 0, 150),
+            literal: Color::rgb(0, 120, 0),
+            identifier: Color::rgb(0, 70, 150),
+            operator: Color::rgb(120, 80, 0),
+            comment: Color::rgb(120, 120, 120),
+        },
+        ui_font: "sans-serif",
+        code_font: "monospace",
+        font_size: 15,
+    };
+
+    pub(crate) const DARK: Theme = Theme {
+        name: "dark",
+        background: Color::rgb(30, 30, 35),
+        text: Color::rgb(220, 220, 220),
+        muted: Color::rgb(150, 150, 150),
+        border: Color::rgb(90, 90, 90),
+        badge_background: Color::rgb(60, 60, 80),
+        primary_highlight: Color::rgb(60, 130, 60),
+        secondary_highlight: Color::rgb(170, 120, 0),
+        changed: Color::rgb(200, 160, 0),
+        error: Color::rgb(240, 90, 90),
+        error_label: Color::rgb(110, 160, 255),
+        note: Color::rgb(120, 200, 120),
+        help: Color::rgb(80, 200, 200),
+        warning: Color::rgb(230, 170, 60),
+        syntax: SyntaxColors {
+            keyword: Color::rgb(220, 130, 220),
+            literal: Color::rgb(140, 210, 140),
+            identifier: Color::rgb(130, 180, 255),
+            operator: Color::rgb(220, 180, 100),
+            comment: Color::rgb(140, 140, 140),
+        },
+        ..Theme::LIGHT
+    };
+
+    // as far apart as possible, with highlights that are light so that the text on them is black
+    pub(crate) const HIGH_CONTRAST: Theme = Theme {
+        name: "high contrast",
+        background: Color::rgb(0, 0, 0),
+        text: Color::rgb(255, 255, 255),
+        muted: Color::rgb(200, 200, 200),
+        border: Color::rgb(255, 255, 255),
+        badge_background: Color::rgb(70, 70, 70),
+        primary_highlight: Color::rgb(255, 255, 0),
+        secondary_highlight: Color::rgb(0, 255, 255),
+        changed: Color::rgb(255, 0, 255),
+        error: Color::rgb(255, 90, 90),
+        error_label: Color::rgb(0, 255, 255),
+        note: Color::rgb(0, 255, 0),
+        help: Color::rgb(0, 255, 255),
+        warning: Color::rgb(255, 200, 0),
+        syntax: SyntaxColors {
+            keyword: Color::rgb(255, 140, 255),
+            literal: Color::rgb(0, 255, 0),
+            identifier: Color::rgb(255, 255, 255),
+            operator: Color::rgb(0, 255, 255),
+            comment: Color::rgb(200, 200, 200),
+        },
+        ..Theme::LIGHT
+    };
+
+    // only uses colors from the okabe-ito palette, which can be told apart without being able to tell red from green
+    pub(crate) const DEUTERANOPIA: Theme = Theme {
+        name: "deuteranopia safe",
+        primary_highlight: Color::rgb(0, 114, 178),
+        secondary_highlight: Color::rgb(230, 159, 0),
+        changed: Color::rgb(240, 228, 66),
+        error: Color::rgb(213, 94, 0),
+        error_label: Color::rgb(0, 114, 178),
+        note: Color::rgb(0, 114, 178),
+        help: Color::rgb(204, 121, 167),
+        warning: Color::rgb(213, 94, 0),
+        syntax: SyntaxColors {
+            keyword: Color::rgb(204, 121, 167),
+            literal: Color::rgb(0, 114, 178),
+            identifier: Color::rgb(20, 20, 20),
+            operator: Color::rgb(213, 94, 0),
+            comment: Color::rgb(120, 120, 120),
+        },
+        ..Theme::LIGHT
+    };
+
+    pub(crate) const ALL: [Theme; 4] = [Theme::LIGHT, Theme::DARK, Theme::HIGH_CONTRAST, Theme::DEUTERANOPIA];
+
+    pub(crate) fn zoomed(self, zoom: u32) -> Theme {
+        Theme { font_size: self.font_size * zoom / 100, ..self }
+    }
+
+    pub(crate) fn label(&self, text: String) -> Label {
+        Label::new(text, self.ui_font.to_string(), self.font_size)
+    }
+    pub(crate) fn code_label(&self, text: String) -> Label {
+        Label::new(text, self.code_font.to_string(), self.font_size)
+    }
+
+    // set on the element that everything is inside of
+    // the changed color is a css variable because it is used by the keyframes in style.css
+    pub(crate) fn root_css(&self) -> String {
+        format!(
+            "background-color: {}; color: {}; font-family: {}; font-size: {}px; min-height: 100vh; --changed-from: {}; --changed-to: {};",
+            self.background.to_css_color(),
+            self.text.to_css_color(),
+            self.ui_font,
+            self.font_size,
+            Color { a: 180, ..self.changed }.to_css_color(),
+            Color { a: 40, ..self.changed }.to_css_color(),
+        )
+    }
+
+    // white text on dark backgrounds and black text on light ones
+    pub(crate) fn text_color_on(background: Color) -> Color {
+        let brightness = u32::from(background.r) * 299 + u32::from(background.g) * 587 + u32::from(background.b) * 114;
+        if brightness < 128_000 {
+            Color::rgb(255, 255, 255)
+        } else {
+            Color::rgb(0, 0, 0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::theme::Theme;
+
+    #[test]
+    fn zoom() {
+        assert_eq!(Theme::LIGHT.zoomed(200).font_size, 30);
+        assert_eq!(Theme::DARK.zoomed(100), Theme::DARK);
+    }
+
+    #[test]
+    fn highlights_are_readable() {
+        // the text on highlights is picked by how bright they are, so it is always one of the two extremes
+        for theme in Theme::ALL {
+            for highlight in [theme.primary_highlight, theme.secondary_highlight] {
+                let text = Theme::text_color_on(highlight);
+                let difference = |a: u8, b: u8| u32::from(a.abs_diff(b));
+                assert!(difference(text.r, highlight.r) + difference(text.g, highlight.g) + difference(text.b, highlight.b) > 300, "{} theme has a highlight that is hard to read", theme.name);
+            }
+        }
+    }
+}
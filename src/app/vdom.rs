@@ -4,8 +4,10 @@ use wasm_bindgen::JsValue;
 
 pub(crate) struct Element<Data: ?Sized> {
     pub(crate) type_: ElementType,
-    pub(crate) props: HashMap<&'static str, JsValue>,
+    pub(crate) props: HashMap<&'static str, Prop>,
     pub(crate) event_listeners: Vec<(&'static str, Box<dyn Fn(JsValue, &mut Data)>)>, // TODO: do not box closures?
+    // each callback is run once after the delay (in milliseconds), which starts again every time that the dom is updated
+    pub(crate) timers: Vec<(u32, Box<dyn Fn(&mut Data)>)>,
     pub(crate) children: Vec<Node<Data>>,
 }
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -14,11 +16,97 @@ pub(crate) enum ElementType {
     P,
     Pre,
     Code,
+    Span,
+    Details,
+    Summary,
+    A,
+    Input,
+    TextArea,
 }
 pub(crate) enum Node<Data: ?Sized> {
     Element(Element<Data>),
     Text(String),
 }
+// the values of props are plain rust values instead of JsValues so that the vdom can be made (and tested) outside of the browser
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) enum Prop {
+    String(String),
+    Number(f64),
+    Bool(bool),
+}
+
+impl Prop {
+    pub(crate) fn as_string(&self) -> Option<String> {
+        match self {
+            Prop::String(s) => Some(s.clone()),
+            Prop::Number(_) | Prop::Bool(_) => None,
+        }
+    }
+
+    pub(crate) fn to_js(&self) -> JsValue {
+        match self {
+            Prop::String(s) => s.into(),
+            Prop::Number(n) => (*n).into(),
+            Prop::Bool(b) => (*b).into(),
+        }
+    }
+}
+
+impl From<String> for Prop {
+    fn from(s: String) -> Self {
+        Prop::String(s)
+    }
+}
+impl From<&str> for Prop {
+    fn from(s: &str) -> Self {
+        Prop::String(s.to_string())
+    }
+}
+impl From<f64> for Prop {
+    fn from(n: f64) -> Self {
+        Prop::Number(n)
+    }
+}
+impl From<i32> for Prop {
+    fn from(n: i32) -> Self {
+        Prop::Number(n.into())
+    }
+}
+impl From<bool> for Prop {
+    fn from(b: bool) -> Self {
+        Prop::Bool(b)
+    }
+}
+
+impl<Data: ?Sized> Element<Data> {
+    // every element inside of this one (including this one), in the order that they are in the document
+    #[cfg(test)]
+    pub(crate) fn descendants(&self) -> Vec<&Element<Data>> {
+        std::iter::once(self)
+            .chain(self.children.iter().flat_map(|child| match child {
+                Node::Element(element) => element.descendants(),
+                Node::Text(_) => Vec::new(),
+            }))
+            .collect()
+    }
+
+    // all of the text inside of the element
+    #[cfg(test)]
+    pub(crate) fn text(&self) -> String {
+        self.children
+            .iter()
+            .map(|child| match child {
+                Node::Element(element) => element.text(),
+                Node::Text(text) => text.clone(),
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn prop(&self, name: &str) -> Option<&Prop> {
+        self.props.get(name)
+    }
+}
 
 impl ElementType {
     pub(crate) fn stringify(&self) -> &'static str {
@@ -27,6 +115,12 @@ impl ElementType {
             ElementType::P => "p",
             ElementType::Pre => "pre",
             ElementType::Code => "code",
+            ElementType::Span => "span",
+            ElementType::Details => "details",
+            ElementType::Summary => "summary",
+            ElementType::A => "a",
+            ElementType::Input => "input",
+            ElementType::TextArea => "textarea",
         }
     }
 }
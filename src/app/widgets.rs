@@ -1,3 +1,4 @@
+pub(crate) mod aria;
 pub(crate) mod center;
 pub(crate) mod clickable;
 pub(crate) mod either;
@@ -5,13 +6,23 @@ pub(crate) mod expand;
 #[macro_use]
 pub(crate) mod flex;
 pub(crate) mod code_view;
+pub(crate) mod css_class;
+pub(crate) mod error_view;
+pub(crate) mod file_picker;
 pub(crate) mod fixed_size;
+pub(crate) mod focus;
+pub(crate) mod inline_style;
 pub(crate) mod label;
+pub(crate) mod link;
 pub(crate) mod max_size;
 pub(crate) mod min_size;
 pub(crate) mod padding;
 pub(crate) mod responds_to_keyboard;
+pub(crate) mod slider;
 pub(crate) mod test_rect;
+pub(crate) mod text_area;
+pub(crate) mod text_input;
+pub(crate) mod timer;
 
 use crate::app::vdom::Element;
 
@@ -0,0 +1,67 @@
+use std::marker::PhantomData;
+
+use crate::app::{vdom, widgets::Widget};
+
+// tells screen readers what the element of the child is, with a role (like "region" or "toolbar") and a label that is read out instead of or together with its contents
+pub(crate) struct Aria<Data, Child: Widget<Data>> {
+    role: Option<&'static str>,
+    label: String,
+    child: Child,
+
+    _phantom: PhantomData<fn(&mut Data)>,
+}
+
+impl<Data, Child: Widget<Data>> Aria<Data, Child> {
+    pub(crate) fn new(role: &'static str, label: String, child: Child) -> Self {
+        Self { role: Some(role), label, child, _phantom: PhantomData }
+    }
+
+    // only a label, for elements that already have a role (like inputs and buttons)
+    pub(crate) fn label(label: String, child: Child) -> Self {
+        Self { role: None, label, child, _phantom: PhantomData }
+    }
+}
+
+impl<Data, Child: Widget<Data>> Widget<Data> for Aria<Data, Child> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        let mut child = self.child.to_vdom();
+        if let Some(role) = self.role {
+            child.props.insert("role", role.into());
+        }
+        child.props.insert("aria-label", self.label.into());
+        child
+    }
+}
+
+// text that is not shown but is read out by screen readers every time that it changes
+// the element has to stay in the same place in the page for screen readers to notice the changes, so it should not be inside of anything that is replaced
+pub(crate) struct LiveRegion {
+    text: String,
+}
+
+impl LiveRegion {
+    pub(crate) fn new(text: String) -> LiveRegion {
+        LiveRegion { text }
+    }
+}
+
+impl<Data> Widget<Data> for LiveRegion {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        vdom::Element {
+            type_: vdom::ElementType::Div,
+            props: [
+                ("role", "status".into()),
+                ("aria-live", "polite".into()),
+                // the whole text is read out instead of only the part that changed
+                ("aria-atomic", "true".into()),
+                // hidden without display: none, which would also hide it from screen readers
+                ("style", "position: absolute; width: 1px; height: 1px; overflow: hidden; clip-path: inset(50%); white-space: nowrap;".into()),
+            ]
+            .into_iter()
+            .collect(),
+            event_listeners: Vec::new(),
+            timers: Vec::new(),
+            children: vec![vdom::Node::Text(self.text)],
+        }
+    }
+}
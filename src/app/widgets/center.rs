@@ -30,6 +30,7 @@ impl<Data, Child: Widget<Data>> Widget<Data> for Center<Data, Child> {
             // TODO: do something about .into_iter().collect() (and remember to change it across the whole project)
             props: vec![("style", "width: 100%; height: 100%; display: flex; justify-content: center; align-items: center;".into())].into_iter().collect(),
             event_listeners: Vec::new(),
+            timers: Vec::new(),
             children: vec![vdom::Node::Element(self.child.to_vdom())],
         }
     }
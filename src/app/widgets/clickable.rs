@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, rc::Rc};
 
 // TODO: REMOVE this whole module (let dom handle click events)
 
@@ -47,11 +47,30 @@ impl<Data, Child: Widget<Data>, Callback: Fn(&mut Data) + 'static> Widget<Data>
             MouseButton::Main => 0,
             MouseButton::Secondary => 2,
         };
+        let on_click = Rc::new(self.on_click);
+        // main button clicks can also be done with the keyboard by focusing the element and pressing enter or space, like with a real button
+        if self.mouse_button == MouseButton::Main {
+            child.props.insert("role", "button".into());
+            child.props.entry("tabIndex").or_insert(0.into());
+            let on_click = Rc::clone(&on_click);
+            child.event_listeners.push((
+                "keydown",
+                Box::new(move |event, data| {
+                    let event = event.dyn_into::<web_sys::KeyboardEvent>().expect("keydown should not recieve event data that is not KeyboardEvent");
+                    if matches!(event.key().as_str(), "Enter" | " ") {
+                        // the keyboard shortcuts around the button should not also get the key
+                        event.prevent_default();
+                        event.stop_propagation();
+                        on_click(data);
+                    }
+                }),
+            ));
+        }
         child.event_listeners.push((
             "click",
             Box::new(move |event, data| {
-                if event.dyn_ref::<web_sys::PointerEvent>().expect("click event received data that is not PointerEvent").button() == button_number_looking_for {
-                    (self.on_click)(data);
+                if event.dyn_ref::<web_sys::MouseEvent>().expect("click event received data that is not MouseEvent").button() == button_number_looking_for {
+                    on_click(data);
                 }
             }),
         ));
@@ -1,21 +1,38 @@
-use std::{collections::HashMap, hash::Hash, ops::Range};
+use std::{
+    collections::{BTreeSet, HashMap},
+    hash::Hash,
+    ops::Range,
+    rc::Rc,
+};
 
 use crate::{
+    app::theme::Theme,
     app::{
         graphics, vdom,
-        widgets::{center::Center, expand::Expand, fixed_size::FixedSize, flex, label::Label, min_size::MinSize, Widget},
+        widgets::{
+            aria::Aria,
+            center::Center,
+            clickable::{Clickable, MouseButton},
+            expand::Expand,
+            fixed_size::FixedSize,
+            flex,
+            min_size::MinSize,
+            Widget,
+        },
     },
+    interpreter::parser::{syntax_classes, SyntaxClass},
     source::Span,
 };
 
 const SHRINK_SCALE_FACTOR: f32 = 0.8; // TODO: put this in a better place
 
-pub(crate) struct LineView<'file> {
-    contents: &'file str,
+pub(crate) struct LineView {
+    line_number: usize,
+    contents: String,
     highlights: Vec<LineHighlight>,
     substitutions: Vec<(Range<usize>, Option<String>)>,
-    font: String, // TODO: custom Font type?
-    font_size: u32,
+    syntax: Vec<(Range<usize>, SyntaxClass)>,
+    theme: Theme,
 }
 
 #[derive(Hash, PartialEq, Eq, Copy, Clone)]
@@ -51,19 +68,60 @@ pub(crate) struct LineViewRenderObject<'file, GetFont: Fn(&graphics::Fonts) -> &
 
 // TODO: messages
 // TODO: scrolling
-// TODO: syntax highlighting
-pub(crate) fn code_view<'file, Data: 'file>(
-    primary_highlight: (Span<'file>, graphics::Color),
-    secondary_highlights: impl IntoIterator<Item = (Span<'file>, graphics::Color)>,
-    substitutions: impl IntoIterator<Item = (Span<'file>, String)>,
-    line_nr_font: String,
-    line_nr_font_size: u32,
-    code_font: String,
-    code_font_size: u32,
-) -> impl Widget<Data> + 'file {
+pub(crate) fn code_view<Data>(
+    primary_highlight: (Span, graphics::Color),
+    secondary_highlights: impl IntoIterator<Item = (Span, graphics::Color)>,
+    substitutions: impl IntoIterator<Item = (Span, String)>,
+    theme: &Theme,
+) -> impl Widget<Data> {
+    let theme = *theme;
+    code_view_with_line_numbers(primary_highlight, secondary_highlights, substitutions, theme, move |line_number| {
+        FixedSize::new(Center::new(theme.label(line_number.to_string())), line_number_size(&theme, 1.4))
+    })
+}
+
+// a code view where clicking on a line number toggles a breakpoint on that line
+pub(crate) fn code_view_with_breakpoints<Data>(
+    primary_highlight: (Span, graphics::Color),
+    secondary_highlights: impl IntoIterator<Item = (Span, graphics::Color)>,
+    substitutions: impl IntoIterator<Item = (Span, String)>,
+    theme: &Theme,
+    breakpoint_lines: BTreeSet<usize>,
+    toggle_breakpoint: impl Fn(&mut Data, usize) + Copy + 'static,
+) -> impl Widget<Data> {
+    let theme = *theme;
+    code_view_with_line_numbers(primary_highlight, secondary_highlights, substitutions, theme, move |line_number| {
+        let (text, label) = if breakpoint_lines.contains(&line_number) {
+            (format!("\u{25cf} {line_number}"), format!("remove breakpoint on line {line_number}"))
+        } else {
+            (line_number.to_string(), format!("add breakpoint on line {line_number}"))
+        };
+        Aria::label(
+            label,
+            Clickable::new(MouseButton::Main, move |data: &mut Data| toggle_breakpoint(data, line_number), FixedSize::new(Center::new(theme.label(text)), line_number_size(&theme, 2.4))),
+        )
+    })
+}
+
+// the line numbers are as tall as a line and get wider and taller with the font size
+fn line_number_size(theme: &Theme, width_in_lines: f32) -> graphics::Vector2f {
+    let line_height = theme.font_size as f32 * 1.4;
+    graphics::Vector2f::new(line_height * width_in_lines, line_height)
+}
+
+// line_number_view is called with the line number (starting at 1) of every line
+fn code_view_with_line_numbers<Data, LineNumberView: Widget<Data>>(
+    primary_highlight: (Span, graphics::Color),
+    secondary_highlights: impl IntoIterator<Item = (Span, graphics::Color)>,
+    substitutions: impl IntoIterator<Item = (Span, String)>,
+    theme: Theme,
+    line_number_view: impl Fn(usize) -> LineNumberView,
+) -> impl Widget<Data> {
     let secondary_highlights: Vec<_> = secondary_highlights.into_iter().collect();
     let substitutions: Vec<_> = substitutions.into_iter().collect();
-    let primary_file = primary_highlight.0.file;
+    let primary_file = Rc::clone(&primary_highlight.0.file);
+    // the lexer decides what is a keyword, so the code view always agrees with the parser
+    let syntax = syntax_classes(&primary_file);
     Expand::new(flex::homogeneous::Flex::new_vertical(
         primary_file
             .lines
@@ -74,7 +132,7 @@ pub(crate) fn code_view<'file, Data: 'file>(
                 let highlights_on_line = std::iter::once(&primary_highlight)
                     .chain(secondary_highlights.iter())
                     .flat_map(|(span, color)| {
-                        if std::ptr::eq(span.file, primary_file) && span_overlaps_line_bounds(span) {
+                        if Rc::ptr_eq(&span.file, &primary_file) && span_overlaps_line_bounds(span) {
                             let highlight_start = if span.start < line_bounds.start { 0 } else { span.start - line_bounds.start };
                             let highlight_end = if span.end > line_bounds.end { line_contents.len() } else { span.end - line_bounds.start };
                             Some(LineHighlight { start: highlight_start, end: highlight_end, color: *color })
@@ -96,19 +154,29 @@ pub(crate) fn code_view<'file, Data: 'file>(
                         }
                     })
                     .collect();
+
+                let syntax_on_line = syntax
+                    .iter()
+                    .filter(|(span, _)| span_overlaps_line_bounds(span))
+                    .map(|(span, class)| (span.start.saturating_sub(line_bounds.start)..std::cmp::min(span.end, line_bounds.end) - line_bounds.start, *class))
+                    .collect();
                 (
                     flex::ItemSettings::Fixed,
                     flex!(horizontal {
-                        line_number: (
-                            flex::ItemSettings::Fixed,
-                            FixedSize::new(Center::new(Label::new((line_number + 1).to_string(), line_nr_font.clone(), line_nr_font_size)), graphics::Vector2f::new(20.0, 20.0))
-                        ), // TODO: also don't hardcode this size, also TODO: line numbers should really be right aligned, not centered
+                        line_number: (flex::ItemSettings::Fixed, line_number_view(line_number + 1)), // TODO: also don't hardcode this size, also TODO: line numbers should really be right aligned, not centered
 
                         line_view: (
                             flex::ItemSettings::Flex(1.0),
                             MinSize::new(
-                                LineView { contents: line_contents, highlights: highlights_on_line, font: code_font.clone(), font_size: code_font_size, substitutions: substitutions_on_line },
-                                graphics::Vector2f::new(0.0, 20.0), // TODO: don't hardcode minimum height
+                                LineView {
+                                    line_number: line_number + 1,
+                                    contents: line_contents.clone(),
+                                    highlights: highlights_on_line,
+                                    theme,
+                                    substitutions: substitutions_on_line,
+                                    syntax: syntax_on_line,
+                                },
+                                graphics::Vector2f::new(0.0, theme.font_size as f32 * 1.4),
                             )
                         ),
                     }),
@@ -118,24 +186,147 @@ pub(crate) fn code_view<'file, Data: 'file>(
     ))
 }
 
-impl<'file, Data> Widget<Data> for LineView<'file> {
+// line numbers start at 1
+pub(crate) fn scroll_to_line(line_number: usize) {
+    let document = web_sys::window().expect("no global window").document().expect("no document on window");
+    if let Some(line) = document.get_element_by_id(&line_element_id(line_number)) {
+        line.scroll_into_view();
+    }
+}
+
+fn line_element_id(line_number: usize) -> String {
+    format!("code-line-{line_number}")
+}
+
+// a piece of a line, in the order that they are shown
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Segment {
+    Text(Piece),
+    // the original text is shrunk and shown under the replacement
+    // the replacement is None on every line after the first one of a substitution that covers more than one line
+    Substitution { range: Range<usize>, replacement: Option<String>, original: Vec<Piece> },
+}
+// text that is all styled the same way
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Piece {
+    range: Range<usize>,
+    highlight: Option<graphics::Color>,
+    syntax: Option<SyntaxClass>,
+}
+
+impl LineView {
+    fn segments(&self) -> Vec<Segment> {
+        let len = self.contents.len();
+        let clamp = |index: usize| (0..=index.min(len)).rev().find(|index| self.contents.is_char_boundary(*index)).unwrap_or(0);
+
+        // text can only be replaced once, so substitutions that overlap one that starts before them are left out
+        let mut sorted_substitutions: Vec<_> = self.substitutions.iter().map(|(range, replacement)| (clamp(range.start)..clamp(range.end), replacement.clone())).collect();
+        sorted_substitutions.sort_by_key(|(range, _)| (range.start, range.end));
+        let mut substitutions: Vec<(Range<usize>, Option<String>)> = Vec::new();
+        for (range, replacement) in sorted_substitutions {
+            if substitutions.last().is_none_or(|(last, _)| last.end <= range.start) {
+                substitutions.push((range, replacement));
+            }
+        }
+
+        let mut boundaries: Vec<usize> = [0, len]
+            .into_iter()
+            .chain(self.highlights.iter().flat_map(|highlight| [clamp(highlight.start), clamp(highlight.end)]))
+            .chain(substitutions.iter().flat_map(|(range, _)| [range.start, range.end]))
+            .chain(self.syntax.iter().flat_map(|(range, _)| [clamp(range.start), clamp(range.end)]))
+            .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+        // highlights that come later are drawn over the ones before them
+        let color_at = |index: usize| self.highlights.iter().rev().find(|highlight| (clamp(highlight.start)..clamp(highlight.end)).contains(&index)).map(|highlight| highlight.color);
+        let syntax_at = |index: usize| self.syntax.iter().find(|(range, _)| (clamp(range.start)..clamp(range.end)).contains(&index)).map(|(_, class)| *class);
+
+        let mut segments = Vec::new();
+        let mut substitutions = substitutions.into_iter().peekable();
+        for piece in boundaries.windows(2).map(|bounds| bounds[0]..bounds[1]) {
+            while let Some((range, replacement)) = substitutions.next_if(|(range, _)| range.start <= piece.start) {
+                segments.push(Segment::Substitution { range, replacement, original: Vec::new() });
+            }
+            let piece = Piece { highlight: color_at(piece.start), syntax: syntax_at(piece.start), range: piece };
+            match segments.last_mut() {
+                Some(Segment::Substitution { range, original, .. }) if range.contains(&piece.range.start) => original.push(piece),
+                _ => segments.push(Segment::Text(piece)),
+            }
+        }
+        // substitutions of nothing at the end of the line
+        segments.extend(substitutions.map(|(range, replacement)| Segment::Substitution { range, replacement, original: Vec::new() }));
+        segments
+    }
+}
+
+fn span<Data>(style: String, children: Vec<vdom::Node<Data>>) -> vdom::Node<Data> {
+    vdom::Node::Element(vdom::Element { type_: vdom::ElementType::Span, props: std::iter::once(("style", style.into())).collect(), event_listeners: Vec::new(), timers: Vec::new(), children })
+}
+
+fn syntax_css(theme: &Theme, class: SyntaxClass) -> String {
+    let colors = theme.syntax;
+    match class {
+        SyntaxClass::Keyword => format!("color: {}; font-weight: bold;", colors.keyword.to_css_color()),
+        SyntaxClass::Literal => format!("color: {};", colors.literal.to_css_color()),
+        SyntaxClass::Identifier => format!("color: {};", colors.identifier.to_css_color()),
+        SyntaxClass::Operator => format!("color: {};", colors.operator.to_css_color()),
+        SyntaxClass::Comment => format!("color: {}; font-style: italic;", colors.comment.to_css_color()),
+    }
+}
+
+// the highlight is drawn as a background image so that it can slide in from the left (the keyframes are in style.css)
+// the animation only plays when the piece becomes highlighted, because the browser only starts an animation when it is added to an element
+fn highlight_css(color: graphics::Color) -> String {
+    let color_css = color.to_css_color();
+    format!(
+        "background-image: linear-gradient({color_css}, {color_css}); background-repeat: no-repeat; background-size: 100% 100%; animation: highlight-in 0.25s ease-out; color: {};",
+        Theme::text_color_on(color).to_css_color()
+    )
+}
+
+fn view_piece<Data>(theme: &Theme, contents: &str, piece: Piece) -> vdom::Node<Data> {
+    // the highlight comes after the syntax style so that its text color wins, which keeps the text readable on dark highlights
+    // every piece is a span even if it has no style so that the pieces are reused between steps, which lets the colors transition
+    let style = std::iter::once("transition: color 0.25s;".to_string()).chain(piece.syntax.map(|class| syntax_css(theme, class))).chain(piece.highlight.map(highlight_css)).collect::<Vec<_>>();
+    span(style.join(" "), vec![vdom::Node::Text(contents[piece.range].to_string())])
+}
+
+impl<Data> Widget<Data> for LineView {
     fn to_vdom(self) -> vdom::Element<Data> {
-        // TODO: showing highlights
-        // TODO: showing substitutions
-        // TODO: adjustable font and size
+        let children = self
+            .segments()
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Text(piece) => view_piece(&self.theme, &self.contents, piece),
+                Segment::Substitution { range: _, replacement, original } => {
+                    // the replacement is on the line with the rest of the code and the original text is shrunk under it
+                    // lines without the replacement still have an empty row so that the original text lines up with the line that it started on
+                    // the replacement fades in while the original text shrinks (the keyframes are in style.css)
+                    let replacement = span("font-weight: bold; animation: substitution-in 0.3s ease-out;".to_string(), vec![vdom::Node::Text(replacement.unwrap_or_else(|| " ".to_string()))]);
+                    let original = span(
+                        format!("font-size: {SHRINK_SCALE_FACTOR}em; opacity: 0.6; animation: substitution-shrink 0.3s ease-out;"),
+                        original.into_iter().map(|piece| view_piece(&self.theme, &self.contents, piece)).collect(),
+                    );
+                    span("display: inline-flex; flex-direction: column; align-items: center; vertical-align: top;".to_string(), vec![replacement, original])
+                }
+            })
+            .collect();
         vdom::Element {
             type_: vdom::ElementType::P,
-            props: HashMap::new(),
+            props: std::iter::once(("id", line_element_id(self.line_number).into())).collect(),
             event_listeners: Vec::new(),
+            timers: Vec::new(),
             children: vec![vdom::Node::Element(vdom::Element {
                 type_: vdom::ElementType::Code,
                 props: HashMap::new(),
                 event_listeners: Vec::new(),
+                timers: Vec::new(),
                 children: vec![vdom::Node::Element(vdom::Element {
                     type_: vdom::ElementType::Pre,
-                    props: HashMap::new(),
+                    props: std::iter::once(("style", format!("font-family: {}; font-size: {}px;", self.theme.code_font, self.theme.font_size).into())).collect(),
                     event_listeners: Vec::new(),
-                    children: vec![vdom::Node::Text(self.contents.to_string())],
+                    timers: Vec::new(),
+                    children,
                 })],
             })],
         }
@@ -526,3 +717,65 @@ fn draw_text_chunk(
     target.draw(&chunk_text);
 }
 */
+
+#[cfg(test)]
+mod test {
+    use std::ops::Range;
+
+    use crate::{
+        app::{
+            graphics::Color,
+            theme::Theme,
+            widgets::code_view::{LineHighlight, LineView, Piece, Segment},
+        },
+        interpreter::parser::SyntaxClass,
+    };
+
+    fn line_view(contents: &str, highlights: Vec<LineHighlight>, substitutions: Vec<(Range<usize>, Option<String>)>, syntax: Vec<(Range<usize>, SyntaxClass)>) -> LineView {
+        LineView { line_number: 1, contents: contents.to_string(), highlights, substitutions, syntax, theme: Theme::LIGHT }
+    }
+
+    fn piece(range: Range<usize>, highlight: Option<Color>) -> Piece {
+        Piece { range, highlight, syntax: None }
+    }
+
+    #[test]
+    fn highlights() {
+        let (green, red) = (Color::rgb(50, 100, 50), Color::rgb(150, 0, 0));
+        let view = line_view("print a + b;", vec![LineHighlight { start: 0, end: 11, color: green }, LineHighlight { start: 6, end: 7, color: red }], Vec::new(), Vec::new());
+        assert_eq!(view.segments(), [Segment::Text(piece(0..6, Some(green))), Segment::Text(piece(6..7, Some(red))), Segment::Text(piece(7..11, Some(green))), Segment::Text(piece(11..12, None))]);
+    }
+
+    #[test]
+    fn substitutions() {
+        let green = Color::rgb(50, 100, 50);
+        let view = line_view("print a + b;", vec![LineHighlight { start: 10, end: 11, color: green }], vec![(6..7, Some("1".to_string())), (6..11, Some("3".to_string())), (10..20, None)], Vec::new());
+        // the substitution that overlaps the first one is left out, and the one that goes past the end of the line stops at the end
+        assert_eq!(
+            view.segments(),
+            [
+                Segment::Text(piece(0..6, None)),
+                Segment::Substitution { range: 6..7, replacement: Some("1".to_string()), original: vec![piece(6..7, None)] },
+                Segment::Text(piece(7..10, None)),
+                Segment::Substitution { range: 10..12, replacement: None, original: vec![piece(10..11, Some(green)), piece(11..12, None)] },
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_under_highlights() {
+        let green = Color::rgb(50, 100, 50);
+        let view = line_view("print a;", vec![LineHighlight { start: 3, end: 7, color: green }], Vec::new(), vec![(0..5, SyntaxClass::Keyword), (6..7, SyntaxClass::Identifier)]);
+        // the pieces are split wherever either the highlight or the syntax class changes
+        assert_eq!(
+            view.segments(),
+            [
+                Segment::Text(Piece { range: 0..3, highlight: None, syntax: Some(SyntaxClass::Keyword) }),
+                Segment::Text(Piece { range: 3..5, highlight: Some(green), syntax: Some(SyntaxClass::Keyword) }),
+                Segment::Text(Piece { range: 5..6, highlight: Some(green), syntax: None }),
+                Segment::Text(Piece { range: 6..7, highlight: Some(green), syntax: Some(SyntaxClass::Identifier) }),
+                Segment::Text(Piece { range: 7..8, highlight: None, syntax: None }),
+            ]
+        );
+    }
+}
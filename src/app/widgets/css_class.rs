@@ -0,0 +1,25 @@
+use std::marker::PhantomData;
+
+use crate::app::{vdom, widgets::Widget};
+
+// gives the element of the child a class so that the rules for that class in style.css apply to it and everything inside of it
+pub(crate) struct CssClass<Data, Child: Widget<Data>> {
+    class: &'static str,
+    child: Child,
+
+    _phantom: PhantomData<fn(&mut Data)>,
+}
+
+impl<Data, Child: Widget<Data>> CssClass<Data, Child> {
+    pub(crate) fn new(class: &'static str, child: Child) -> Self {
+        Self { class, child, _phantom: PhantomData }
+    }
+}
+
+impl<Data, Child: Widget<Data>> Widget<Data> for CssClass<Data, Child> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        let mut child = self.child.to_vdom();
+        child.props.insert("className", self.class.into());
+        child
+    }
+}
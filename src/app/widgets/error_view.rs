@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::{
+    app::{theme::Theme, vdom, widgets::Widget},
+    error::{
+        catalog::Explanation,
+        render::{self, Style, StyledLine},
+        Error,
+    },
+};
+
+pub(crate) struct ErrorView {
+    lines: Vec<StyledLine>,
+    explanation: Option<&'static Explanation>,
+    theme: Theme,
+}
+
+impl ErrorView {
+    pub(crate) fn new(error: &Error, theme: &Theme) -> ErrorView {
+        ErrorView { lines: render::render(error), explanation: error.explanation, theme: *theme }
+    }
+}
+
+impl<Data> Widget<Data> for ErrorView {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        let children = self
+            .lines
+            .into_iter()
+            .flat_map(|line| {
+                line.into_iter()
+                    .map(|(style, text)| {
+                        vdom::Node::Element(vdom::Element {
+                            type_: vdom::ElementType::Span,
+                            props: std::iter::once(("style", style_css(&self.theme, style).into())).collect(),
+                            event_listeners: Vec::new(),
+                            timers: Vec::new(),
+                            children: vec![vdom::Node::Text(text)],
+                        })
+                    })
+                    .chain(std::iter::once(vdom::Node::Text("\n".to_string())))
+            })
+            .collect();
+
+        let rendered = vdom::Node::Element(vdom::Element {
+            type_: vdom::ElementType::Pre,
+            props: std::iter::once(("style", format!("font-family: {};", self.theme.code_font).into())).collect(),
+            event_listeners: Vec::new(),
+            timers: Vec::new(),
+            children,
+        });
+        vdom::Element {
+            type_: vdom::ElementType::Div,
+            props: HashMap::new(),
+            event_listeners: Vec::new(),
+            timers: Vec::new(),
+            children: std::iter::once(rendered).chain(self.explanation.map(|explanation| view_explanation(&self.theme, explanation))).collect(),
+        }
+    }
+}
+
+// uses a <details> element so that the explanation can be opened and closed without needing any state
+fn view_explanation<Data>(theme: &Theme, explanation: &Explanation) -> vdom::Node<Data> {
+    let element = |type_, props: HashMap<_, _>, children| vdom::Node::Element(vdom::Element { type_, props, event_listeners: Vec::new(), timers: Vec::new(), children });
+    let text = |type_, text: &str| element(type_, HashMap::new(), vec![vdom::Node::Text(text.to_string())]);
+
+    let summary_style = format!("color: {}; text-decoration: underline; cursor: pointer;", theme.error_label.to_css_color());
+    let mut children = vec![
+        element(vdom::ElementType::Summary, std::iter::once(("style", summary_style.into())).collect(), vec![vdom::Node::Text("what does this mean?".to_string())]),
+        text(vdom::ElementType::P, &format!("{}: {}", explanation.code, explanation.title)),
+    ];
+    children.extend(explanation.explanation.split("\n\n").map(|paragraph| text(vdom::ElementType::P, paragraph)));
+    children.extend([
+        text(vdom::ElementType::P, "for example, this program has this error:"),
+        text(vdom::ElementType::Pre, explanation.wrong_example),
+        text(vdom::ElementType::P, "and this is one way to fix it:"),
+        text(vdom::ElementType::Pre, explanation.fixed_example),
+    ]);
+
+    element(vdom::ElementType::Details, HashMap::new(), children)
+}
+
+fn style_css(theme: &Theme, style: Style) -> String {
+    match style {
+        Style::Plain => String::new(),
+        Style::Header => "font-weight: bold;".to_string(),
+        Style::Gutter => format!("color: {};", theme.error_label.to_css_color()),
+        Style::Primary => format!("color: {}; font-weight: bold;", theme.error.to_css_color()),
+        Style::Secondary => format!("color: {};", theme.error_label.to_css_color()),
+        Style::Note => format!("color: {};", theme.note.to_css_color()),
+        Style::Help => format!("color: {};", theme.help.to_css_color()),
+    }
+}
@@ -0,0 +1,80 @@
+use std::marker::PhantomData;
+
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::js_sys;
+
+use crate::app::{vdom, widgets::Widget};
+
+// reading the file happens asynchronously but the data can only be changed inside of event listeners,
+// so once the file is read, this custom event is sent to the input element with the file name and contents
+const FILE_LOADED_EVENT: &str = "pel-file-loaded";
+
+pub(crate) struct FilePicker<Data, Callback: Fn(&mut Data, String, String)> {
+    accept: &'static str,
+    on_load: Callback, // called with the name and the contents of the file
+
+    _phantom: PhantomData<fn(&mut Data)>,
+}
+
+impl<Data, Callback: Fn(&mut Data, String, String)> FilePicker<Data, Callback> {
+    pub(crate) fn new(accept: &'static str, on_load: Callback) -> Self {
+        Self { accept, on_load, _phantom: PhantomData }
+    }
+}
+
+impl<Data, Callback: Fn(&mut Data, String, String) + 'static> Widget<Data> for FilePicker<Data, Callback> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        vdom::Element {
+            type_: vdom::ElementType::Input,
+            props: [("type", "file".into()), ("accept", self.accept.into())].into_iter().collect(),
+            event_listeners: vec![
+                ("change", Box::new(|event, _| start_reading(event))),
+                (
+                    FILE_LOADED_EVENT,
+                    Box::new(move |event, data| {
+                        let detail: js_sys::Array = event.dyn_into::<web_sys::CustomEvent>().expect("file loaded event should be CustomEvent").detail().unchecked_into();
+                        let name = detail.get(0).as_string().expect("file loaded event should have file name");
+                        let contents = detail.get(1).as_string().expect("file loaded event should have file contents");
+                        (self.on_load)(data, name, contents);
+                    }),
+                ),
+            ],
+            timers: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+fn start_reading(event: JsValue) {
+    let Some(input) = event.dyn_into::<web_sys::Event>().ok().and_then(|event| event.target()).and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok()) else {
+        return;
+    };
+    let Some(file) = input.files().and_then(|files| files.get(0)) else {
+        return;
+    };
+    let Ok(reader) = web_sys::FileReader::new() else {
+        return;
+    };
+
+    let onload = Closure::once_into_js({
+        let reader = reader.clone();
+        let name = file.name();
+        move || {
+            let Some(contents) = reader.result().ok().and_then(|result| result.as_string()) else {
+                web_sys::console::warn_1(&"could not read file as text".into());
+                return;
+            };
+            let mut init = web_sys::CustomEventInit::new();
+            init.detail(&js_sys::Array::of2(&name.into(), &contents.into()));
+            if let Ok(loaded_event) = web_sys::CustomEvent::new_with_event_init_dict(FILE_LOADED_EVENT, &init) {
+                let _ = input.dispatch_event(&loaded_event);
+            }
+            // clear the input so that picking the same file again still counts as a change
+            input.set_value("");
+        }
+    });
+    reader.set_onload(Some(onload.unchecked_ref()));
+    if reader.read_as_text(&file).is_err() {
+        web_sys::console::warn_1(&"could not start reading file".into());
+    }
+}
@@ -24,6 +24,7 @@ impl<Data, Child: Widget<Data>> Widget<Data> for FixedSize<Data, Child> {
             // TODO: do something about .into_iter().collect() (and remember to change it across the whole project)
             props: vec![("style", format!("width: {}px; height: {}px;", self.size.x, self.size.y).into())].into_iter().collect(),
             event_listeners: Vec::new(),
+            timers: Vec::new(),
             children: vec![vdom::Node::Element(self.child.to_vdom())],
         }
     }
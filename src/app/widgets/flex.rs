@@ -30,6 +30,7 @@ pub(crate) mod _layout {
             ))
             .collect(),
             event_listeners: Vec::new(),
+            timers: Vec::new(),
             children: children
                 .into_iter()
                 .map(|(settings, mut child)| {
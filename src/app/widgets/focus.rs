@@ -0,0 +1,69 @@
+use std::{marker::PhantomData, rc::Rc};
+
+use crate::app::{vdom, widgets::Widget};
+
+// shows a widget that only works on part of the data
+// the part might not always exist (for example if it is in one variant of an enum), and events are ignored when it does not
+pub(crate) struct Focus<Data, ChildData, Child: Widget<ChildData>, Project: Fn(&mut Data) -> Option<&mut ChildData>> {
+    project: Project,
+    child: Child,
+
+    _phantom: PhantomData<(fn(&mut Data), fn(&mut ChildData))>,
+}
+
+impl<Data, ChildData, Child: Widget<ChildData>, Project: Fn(&mut Data) -> Option<&mut ChildData>> Focus<Data, ChildData, Child, Project> {
+    pub(crate) fn new(project: Project, child: Child) -> Self {
+        Self { project, child, _phantom: PhantomData }
+    }
+}
+
+impl<Data: 'static, ChildData: 'static, Child: Widget<ChildData>, Project: Fn(&mut Data) -> Option<&mut ChildData> + 'static> Widget<Data> for Focus<Data, ChildData, Child, Project> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        focus_element(self.child.to_vdom(), &(Rc::new(self.project) as Rc<dyn Fn(&mut Data) -> Option<&mut ChildData>>))
+    }
+}
+
+fn focus_element<Data: 'static, ChildData: 'static>(element: vdom::Element<ChildData>, project: &Rc<dyn Fn(&mut Data) -> Option<&mut ChildData>>) -> vdom::Element<Data> {
+    vdom::Element {
+        type_: element.type_,
+        props: element.props,
+        event_listeners: element
+            .event_listeners
+            .into_iter()
+            .map(|(event, listener)| {
+                let project = Rc::clone(project);
+                (
+                    event,
+                    Box::new(move |event, data: &mut Data| {
+                        if let Some(child_data) = project(data) {
+                            listener(event, child_data);
+                        }
+                    }) as Box<dyn Fn(_, &mut Data)>,
+                )
+            })
+            .collect(),
+        timers: element
+            .timers
+            .into_iter()
+            .map(|(delay, callback)| {
+                let project = Rc::clone(project);
+                (
+                    delay,
+                    Box::new(move |data: &mut Data| {
+                        if let Some(child_data) = project(data) {
+                            callback(child_data);
+                        }
+                    }) as Box<dyn Fn(&mut Data)>,
+                )
+            })
+            .collect(),
+        children: element
+            .children
+            .into_iter()
+            .map(|child| match child {
+                vdom::Node::Element(element) => vdom::Node::Element(focus_element(element, project)),
+                vdom::Node::Text(text) => vdom::Node::Text(text),
+            })
+            .collect(),
+    }
+}
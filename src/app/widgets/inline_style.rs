@@ -0,0 +1,26 @@
+use std::marker::PhantomData;
+
+use crate::app::{vdom, widgets::Widget};
+
+// adds css to the style of the element of the child, after the style that it already has
+pub(crate) struct InlineStyle<Data, Child: Widget<Data>> {
+    css: String,
+    child: Child,
+
+    _phantom: PhantomData<fn(&mut Data)>,
+}
+
+impl<Data, Child: Widget<Data>> InlineStyle<Data, Child> {
+    pub(crate) fn new(css: String, child: Child) -> Self {
+        Self { css, child, _phantom: PhantomData }
+    }
+}
+
+impl<Data, Child: Widget<Data>> Widget<Data> for InlineStyle<Data, Child> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        let mut child = self.child.to_vdom();
+        let style = child.props.get("style").and_then(|style| style.as_string()).unwrap_or_default();
+        child.props.insert("style", (style + " " + &self.css).into());
+        child
+    }
+}
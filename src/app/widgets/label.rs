@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use crate::app::{vdom, widgets::Widget};
 
 // ideally this would just have a reference to the Font object but rust doenst support higher kinded type parameters so i couldnt get it to work
@@ -27,8 +25,13 @@ impl Label {
 
 impl<Data> Widget<Data> for Label {
     fn to_vdom(self) -> vdom::Element<Data> {
-        // TODO: font, font size
-        vdom::Element { type_: vdom::ElementType::P, props: HashMap::new(), event_listeners: vec![], children: vec![vdom::Node::Text(self.text)] }
+        vdom::Element {
+            type_: vdom::ElementType::P,
+            props: std::iter::once(("style", format!("font-family: {}; font-size: {}px;", self.font, self.font_size).into())).collect(),
+            event_listeners: vec![],
+            timers: Vec::new(),
+            children: vec![vdom::Node::Text(self.text)],
+        }
     }
 }
 
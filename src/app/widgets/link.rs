@@ -0,0 +1,24 @@
+use crate::app::{vdom, widgets::Widget};
+
+pub(crate) struct Link {
+    text: String,
+    href: String,
+}
+
+impl Link {
+    pub(crate) fn new(text: String, href: String) -> Link {
+        Link { text, href }
+    }
+}
+
+impl<Data> Widget<Data> for Link {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        vdom::Element {
+            type_: vdom::ElementType::A,
+            props: [("href", self.href.into())].into_iter().collect(),
+            event_listeners: Vec::new(),
+            timers: Vec::new(),
+            children: vec![vdom::Node::Text(self.text)],
+        }
+    }
+}
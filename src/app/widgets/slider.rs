@@ -0,0 +1,51 @@
+use std::marker::PhantomData;
+
+use wasm_bindgen::JsCast;
+
+use crate::app::{vdom, widgets::Widget};
+
+pub(crate) struct Slider<Data, Callback: Fn(&mut Data, usize)> {
+    min: usize,
+    max: usize,
+    value: usize,
+    on_change: Callback,
+
+    _phantom: PhantomData<fn(&mut Data)>,
+}
+
+impl<Data, Callback: Fn(&mut Data, usize)> Slider<Data, Callback> {
+    pub(crate) fn new(min: usize, max: usize, value: usize, on_change: Callback) -> Self {
+        Self { min, max, value, on_change, _phantom: PhantomData }
+    }
+}
+
+impl<Data, Callback: Fn(&mut Data, usize) + 'static> Widget<Data> for Slider<Data, Callback> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        vdom::Element {
+            type_: vdom::ElementType::Input,
+            props: [("type", "range".into()), ("min", (self.min as f64).into()), ("max", (self.max as f64).into()), ("step", 1.into()), ("value", (self.value as f64).into())].into_iter().collect(),
+            event_listeners: vec![
+                (
+                    "input",
+                    Box::new(move |event, data| {
+                        let Some(input) = event.dyn_into::<web_sys::Event>().ok().and_then(|event| event.target()).and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok()) else {
+                            return;
+                        };
+                        (self.on_change)(data, input.value_as_number() as usize);
+                    }),
+                ),
+                // the arrow keys already move the slider, so they should not also reach keyboard shortcuts of the widgets around it
+                (
+                    "keydown",
+                    Box::new(|event, _| {
+                        if let Ok(event) = event.dyn_into::<web_sys::Event>() {
+                            event.stop_propagation();
+                        }
+                    }),
+                ),
+            ],
+            timers: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
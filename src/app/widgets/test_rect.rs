@@ -27,6 +27,7 @@ impl<Data> Widget<Data> for TestRect {
             type_: vdom::ElementType::Div,
             props: vec![("style", format!("width: {}px; height: {}px; color: {}", self.size.x, self.size.y, self.color.to_css_color()).into())].into_iter().collect(),
             event_listeners: Vec::new(),
+            timers: Vec::new(),
             children: Vec::new(),
         }
     }
@@ -0,0 +1,57 @@
+use std::marker::PhantomData;
+
+use wasm_bindgen::JsCast;
+
+use crate::app::{vdom, widgets::Widget};
+
+// a box for editing text that has more than one line, like source code
+pub(crate) struct TextArea<Data, Callback: Fn(&mut Data, String)> {
+    value: String,
+    on_input: Callback, // called on every change, unlike TextInput
+
+    _phantom: PhantomData<fn(&mut Data)>,
+}
+
+impl<Data, Callback: Fn(&mut Data, String)> TextArea<Data, Callback> {
+    pub(crate) fn new(value: String, on_input: Callback) -> Self {
+        Self { value, on_input, _phantom: PhantomData }
+    }
+}
+
+impl<Data, Callback: Fn(&mut Data, String) + 'static> Widget<Data> for TextArea<Data, Callback> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        vdom::Element {
+            type_: vdom::ElementType::TextArea,
+            // setting the value to what it already is does not move the cursor, so the value can be set on every update
+            props: [
+                ("value", self.value.into()),
+                ("spellcheck", false.into()),
+                ("style", "width: 100%; height: 100%; min-height: 300px; box-sizing: border-box; font-family: monospace; font-size: 15px; tab-size: 4;".into()),
+            ]
+            .into_iter()
+            .collect(),
+            event_listeners: vec![
+                (
+                    "input",
+                    Box::new(move |event, data| {
+                        let Some(text_area) = event.dyn_into::<web_sys::Event>().ok().and_then(|event| event.target()).and_then(|target| target.dyn_into::<web_sys::HtmlTextAreaElement>().ok()) else {
+                            return;
+                        };
+                        (self.on_input)(data, text_area.value());
+                    }),
+                ),
+                // typing should not trigger the keyboard shortcuts of the widgets around it
+                (
+                    "keydown",
+                    Box::new(|event, _| {
+                        if let Ok(event) = event.dyn_into::<web_sys::Event>() {
+                            event.stop_propagation();
+                        }
+                    }),
+                ),
+            ],
+            timers: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::marker::PhantomData;
+
+use wasm_bindgen::JsCast;
+
+use crate::app::{vdom, widgets::Widget};
+
+pub(crate) struct TextInput<Data, Callback: Fn(&mut Data, String)> {
+    value: String,
+    placeholder: String,
+    on_change: Callback, // called when the text is committed (when enter is pressed or the input loses focus), not on every key press
+
+    _phantom: PhantomData<fn(&mut Data)>,
+}
+
+impl<Data, Callback: Fn(&mut Data, String)> TextInput<Data, Callback> {
+    pub(crate) fn new(value: String, placeholder: String, on_change: Callback) -> Self {
+        Self { value, placeholder, on_change, _phantom: PhantomData }
+    }
+}
+
+impl<Data, Callback: Fn(&mut Data, String) + 'static> Widget<Data> for TextInput<Data, Callback> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        vdom::Element {
+            type_: vdom::ElementType::Input,
+            props: [("type", "text".into()), ("placeholder", self.placeholder.into()), ("value", self.value.into())].into_iter().collect(),
+            event_listeners: vec![
+                (
+                    "change",
+                    Box::new(move |event, data| {
+                        let Some(input) = event.dyn_into::<web_sys::Event>().ok().and_then(|event| event.target()).and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok()) else {
+                            return;
+                        };
+                        (self.on_change)(data, input.value());
+                    }),
+                ),
+                // typing should not trigger the keyboard shortcuts of the widgets around it
+                (
+                    "keydown",
+                    Box::new(|event, _| {
+                        if let Ok(event) = event.dyn_into::<web_sys::Event>() {
+                            event.stop_propagation();
+                        }
+                    }),
+                ),
+            ],
+            timers: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
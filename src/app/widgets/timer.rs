@@ -0,0 +1,27 @@
+use std::marker::PhantomData;
+
+use crate::app::{vdom, widgets::Widget};
+
+// calls on_fire after the delay (in milliseconds)
+// the delay starts again every time that the widget is shown, so calling on_fire again is done by showing the timer again after it fires
+pub(crate) struct Timer<Data, Child: Widget<Data>, Callback: Fn(&mut Data)> {
+    delay: u32,
+    on_fire: Callback,
+    child: Child,
+
+    _phantom: PhantomData<fn(&mut Data)>,
+}
+
+impl<Data, Child: Widget<Data>, Callback: Fn(&mut Data)> Timer<Data, Child, Callback> {
+    pub(crate) fn new(delay: u32, on_fire: Callback, child: Child) -> Self {
+        Self { delay, on_fire, child, _phantom: PhantomData }
+    }
+}
+
+impl<Data, Child: Widget<Data>, Callback: Fn(&mut Data) + 'static> Widget<Data> for Timer<Data, Child, Callback> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        let mut child = self.child.to_vdom();
+        child.timers.push((self.delay, Box::new(self.on_fire)));
+        child
+    }
+}
@@ -0,0 +1,265 @@
+use std::{io::Write, process::ExitCode, rc::Rc, time::Duration};
+
+use crate::{
+    error::{catalog, render::render_plain, Error},
+    interpreter::{
+        interpreter::{run_headless, run_traced, Budget},
+        parser::{lex, parse_statements},
+    },
+    source::File,
+};
+
+const USAGE: &str = "usage: pel <command> [argument] [options]
+
+commands:
+    run <file>            run a program and print its output
+    check <file>          check a program for errors without running it
+    trace <file>          run a program and print every step that it takes as json lines
+    dump-tokens <file>    print the tokens that a program is made of
+    dump-ast <file>       print the syntax tree of a program
+    explain <code>        show the long form explanation of an error code (like 'E0001')
+    help                  show this message
+
+options for 'run' and 'trace':
+    --max-steps <steps>   stop the program if it has not finished after this many steps
+    --max-time <seconds>  stop the program if it has not finished after this many seconds
+
+exit codes:
+    0     success
+    1     the program has errors that prevent it from running
+    2     the program stopped because of an error while it was running
+    64    the command line arguments are invalid
+    66    the file could not be read";
+
+// these follow the codes from sysexits.h where there is one that fits
+const EXIT_PARSE_ERROR: u8 = 1;
+const EXIT_RUNTIME_ERROR: u8 = 2;
+const EXIT_USAGE: u8 = 64;
+const EXIT_NO_INPUT: u8 = 66;
+
+#[derive(PartialEq, Eq, Debug)]
+enum Command {
+    Run(String, Budget),
+    Check(String),
+    Trace(String, Budget),
+    DumpTokens(String),
+    DumpAst(String),
+    Explain(String),
+    Help,
+}
+
+pub fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match parse_args(&args) {
+        Ok(command) => run_command(command),
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            ExitCode::from(EXIT_USAGE)
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let (command, rest) = args.split_first().ok_or_else(|| "no command given".to_string())?;
+    let argument = |name: &str| match rest {
+        [argument] => Ok(argument.clone()),
+        [] => Err(format!("'{command}' needs a {name}")),
+        _ => Err(format!("too many arguments to '{command}'")),
+    };
+    match command.as_str() {
+        "run" => {
+            let (file, budget) = file_and_budget(command, rest)?;
+            Ok(Command::Run(file, budget))
+        }
+        "check" => Ok(Command::Check(argument("file")?)),
+        "trace" => {
+            let (file, budget) = file_and_budget(command, rest)?;
+            Ok(Command::Trace(file, budget))
+        }
+        "dump-tokens" => Ok(Command::DumpTokens(argument("file")?)),
+        "dump-ast" => Ok(Command::DumpAst(argument("file")?)),
+        "explain" => Ok(Command::Explain(argument("error code")?)),
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        _ => Err(format!("unknown command '{command}'")),
+    }
+}
+
+fn file_and_budget(command: &str, args: &[String]) -> Result<(String, Budget), String> {
+    let mut file = None;
+    let mut budget = Budget::UNLIMITED;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("'{arg}' needs a {name}"));
+        match arg.as_str() {
+            "--max-steps" => {
+                let steps = value("number of steps")?;
+                budget.max_steps = Some(steps.parse().map_err(|_| format!("invalid number of steps '{steps}'"))?);
+            }
+            "--max-time" => {
+                let seconds = value("number of seconds")?;
+                budget.max_time = Some(seconds.parse().ok().and_then(|seconds| Duration::try_from_secs_f64(seconds).ok()).ok_or_else(|| format!("invalid number of seconds '{seconds}'"))?);
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{arg}'")),
+            _ if file.is_none() => file = Some(arg.clone()),
+            _ => return Err(format!("too many arguments to '{command}'")),
+        }
+    }
+    Ok((file.ok_or_else(|| format!("'{command}' needs a file"))?, budget))
+}
+
+fn run_command(command: Command) -> ExitCode {
+    let result = match command {
+        Command::Run(path, budget) => run(&path, budget),
+        Command::Check(path) => check(&path),
+        Command::Trace(path, budget) => trace(&path, budget),
+        Command::DumpTokens(path) => dump_tokens(&path),
+        Command::DumpAst(path) => dump_ast(&path),
+        Command::Explain(code) => explain(&code),
+        Command::Help => {
+            let _ = writeln!(std::io::stdout().lock(), "{USAGE}");
+            Ok(())
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(code) => ExitCode::from(code),
+    }
+}
+
+fn run(path: &str, budget: Budget) -> Result<(), u8> {
+    let file = read_file(path)?;
+    let stmts = parse_statements(&file).map_err(|errors| report_errors(&errors, EXIT_PARSE_ERROR))?;
+
+    let mut stdout = std::io::stdout();
+    let result = run_headless(stmts, budget, |output| {
+        // flushed every time so that the output of long running programs shows up as it is printed
+        let _ = stdout.write_all(output.as_bytes());
+        let _ = stdout.flush();
+    });
+    result.map_err(|err| report_errors(&[(&err).into()], EXIT_RUNTIME_ERROR))
+}
+
+fn check(path: &str) -> Result<(), u8> {
+    let file = read_file(path)?;
+    parse_statements(&file).map_err(|errors| report_errors(&errors, EXIT_PARSE_ERROR))?;
+    Ok(())
+}
+
+// the format of the trace is described in interpreter::interpreter::trace
+fn trace(path: &str, budget: Budget) -> Result<(), u8> {
+    let file = read_file(path)?;
+    let stmts = parse_statements(&file).map_err(|errors| report_errors(&errors, EXIT_PARSE_ERROR))?;
+
+    let mut stdout = std::io::stdout().lock();
+    let result = run_traced(stmts, budget, |line| {
+        let _ = writeln!(stdout, "{line}");
+    });
+    result.map_err(|err| report_errors(&[(&err).into()], EXIT_RUNTIME_ERROR))
+}
+
+fn dump_tokens(path: &str) -> Result<(), u8> {
+    let file = read_file(path)?;
+    let (tokens, errors) = lex(&file);
+    let mut stdout = std::io::stdout().lock();
+    for token in tokens {
+        // a closed pipe (like 'pel dump-tokens a.pel | head') is not an error, everything after it is just not printed
+        if writeln!(stdout, "{:?} {:?}", token.0, token.1).is_err() {
+            break;
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(report_errors(&errors, EXIT_PARSE_ERROR))
+    }
+}
+
+fn dump_ast(path: &str) -> Result<(), u8> {
+    let file = read_file(path)?;
+    let stmts = parse_statements(&file).map_err(|errors| report_errors(&errors, EXIT_PARSE_ERROR))?;
+    let _ = writeln!(std::io::stdout().lock(), "{stmts:#?}");
+    Ok(())
+}
+
+fn explain(code: &str) -> Result<(), u8> {
+    let Some(explanation) = catalog::lookup(code) else {
+        eprintln!("error: there is no error with the code '{code}'");
+        return Err(EXIT_USAGE);
+    };
+    let indent = |example: &str| example.lines().map(|line| format!("    {line}\n")).collect::<String>();
+    let _ = writeln!(
+        std::io::stdout().lock(),
+        "{}: {}\n\n{}\n\nfor example, this program has this error:\n\n{}\nit can be fixed like this:\n\n{}",
+        explanation.code,
+        explanation.title,
+        explanation.explanation,
+        indent(explanation.wrong_example),
+        indent(explanation.fixed_example)
+    );
+    Ok(())
+}
+
+fn read_file(path: &str) -> Result<Rc<File>, u8> {
+    match std::fs::read_to_string(path) {
+        Ok(source) => Ok(File::new(path.to_string(), source)),
+        Err(err) => {
+            eprintln!("error: could not read '{path}': {err}");
+            Err(EXIT_NO_INPUT)
+        }
+    }
+}
+
+// returns the exit code so that it can be used directly in map_err
+fn report_errors(errors: &[Error], exit_code: u8) -> u8 {
+    for error in errors {
+        eprintln!("{}\n", render_plain(error));
+    }
+    if let Some(explanation) = errors.iter().find_map(|error| error.explanation) {
+        eprintln!("for more information about an error, try 'pel explain {}'", explanation.code);
+    }
+    exit_code
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{
+        cli::{parse_args, Command},
+        interpreter::interpreter::Budget,
+    };
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(parse_args(&args(&["run", "a.pel"])), Ok(Command::Run("a.pel".to_string(), Budget::UNLIMITED)));
+        assert_eq!(parse_args(&args(&["trace", "a.pel"])), Ok(Command::Trace("a.pel".to_string(), Budget::UNLIMITED)));
+        assert_eq!(parse_args(&args(&["dump-ast", "a.pel"])), Ok(Command::DumpAst("a.pel".to_string())));
+        assert_eq!(parse_args(&args(&["explain", "e0001"])), Ok(Command::Explain("e0001".to_string())));
+        assert_eq!(parse_args(&args(&["--help"])), Ok(Command::Help));
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(parse_args(&args(&[])), Err("no command given".to_string()));
+        assert_eq!(parse_args(&args(&["run"])), Err("'run' needs a file".to_string()));
+        assert_eq!(parse_args(&args(&["check", "a.pel", "b.pel"])), Err("too many arguments to 'check'".to_string()));
+        assert_eq!(parse_args(&args(&["frobnicate"])), Err("unknown command 'frobnicate'".to_string()));
+    }
+
+    #[test]
+    fn budget_options() {
+        assert_eq!(
+            parse_args(&args(&["run", "--max-steps", "100", "a.pel", "--max-time", "1.5"])),
+            Ok(Command::Run("a.pel".to_string(), Budget { max_steps: Some(100), max_time: Some(Duration::from_millis(1500)) }))
+        );
+        assert_eq!(parse_args(&args(&["trace", "a.pel", "--max-steps"])), Err("'--max-steps' needs a number of steps".to_string()));
+        assert_eq!(parse_args(&args(&["run", "a.pel", "--max-steps", "lots"])), Err("invalid number of steps 'lots'".to_string()));
+        assert_eq!(parse_args(&args(&["run", "a.pel", "--max-time", "-1"])), Err("invalid number of seconds '-1'".to_string()));
+        assert_eq!(parse_args(&args(&["run", "--max-steps", "5"])), Err("'run' needs a file".to_string()));
+        assert_eq!(parse_args(&args(&["run", "a.pel", "--fast"])), Err("unknown option '--fast'".to_string()));
+    }
+}
@@ -1,40 +1,67 @@
-use crate::source::Span;
+pub(crate) mod catalog;
+pub(crate) mod render;
+pub(crate) mod suggestion;
 
-pub struct ErrorReportedPromise(());
+use crate::{error::catalog::Explanation, source::Span};
 
-pub(crate) struct Error<'file> {
-    main_message: String,
-    span: Option<Span<'file>>,
-}
-impl Error<'_> {
-    pub(crate) fn new(span: Option<Span<'_>>, main_message: String) -> Error<'_> {
-        Error { main_message, span }
-    }
-}
+pub struct ErrorReportedPromise(());
 
-mod private {
-    pub trait Sealed {}
+pub(crate) struct Error {
+    pub(crate) main_message: String,
+    pub(crate) explanation: Option<&'static Explanation>,
+    pub(crate) span: Option<Span>,
+    pub(crate) labels: Vec<(Span, String)>,
+    // notes and help are kept in one list so that they are shown in the order that they were added
+    pub(crate) footnotes: Vec<Footnote>,
 }
-pub trait Report: private::Sealed {
-    fn report(self) -> ErrorReportedPromise;
+pub(crate) enum Footnote {
+    Note(String),
+    // the replacement is a span of the source code and what it should be replaced with, so that the ui can apply the fix
+    Help { message: String, replacement: Option<(Span, String)> },
 }
+impl Error {
+    pub(crate) fn new(span: Option<Span>, main_message: String) -> Error {
+        Error { main_message, explanation: None, span, labels: Vec::new(), footnotes: Vec::new() }
+    }
 
-impl<'file, T: Into<Error<'file>>> private::Sealed for T {}
-impl<'file, T: Into<Error<'file>>> Report for T {
-    fn report(self) -> ErrorReportedPromise {
-        report(self.into())
+    pub(crate) fn with_code(mut self, explanation: &'static Explanation) -> Error {
+        self.explanation = Some(explanation);
+        self
+    }
+    pub(crate) fn with_label(mut self, span: Span, label: String) -> Error {
+        self.labels.push((span, label));
+        self
+    }
+    pub(crate) fn with_note(mut self, note: String) -> Error {
+        self.footnotes.push(Footnote::Note(note));
+        self
+    }
+    pub(crate) fn with_help(mut self, help: String) -> Error {
+        self.footnotes.push(Footnote::Help { message: help, replacement: None });
+        self
+    }
+    pub(crate) fn with_suggestion(mut self, help: String, span: Span, replacement: String) -> Error {
+        self.footnotes.push(Footnote::Help { message: help, replacement: Some((span, replacement)) });
+        self
     }
 }
 
-fn report(error: Error) -> ErrorReportedPromise {
-    // TODO: dont put this in the console
-    if let Some(span) = error.span {
-        web_sys::console::log_1(&format!("error at {span}: {}", error.main_message).into());
-    } else {
-        web_sys::console::log_1(&format!("error: {}", error.main_message).into());
+// errors are collected as they are reported instead of being shown right away so that they can all be shown together
+pub(crate) struct ErrorCollector {
+    errors: Vec<Error>,
+}
+impl ErrorCollector {
+    pub(crate) fn new() -> ErrorCollector {
+        ErrorCollector { errors: Vec::new() }
     }
 
-    // TODO: do this better
+    pub(crate) fn report(&mut self, error: impl Into<Error>) -> ErrorReportedPromise {
+        self.errors.push(error.into());
+        ErrorReportedPromise(())
+    }
 
-    ErrorReportedPromise(())
+    pub(crate) fn finish(mut self) -> Vec<Error> {
+        self.errors.sort_by_key(|error| error.span.as_ref().map(|span| span.start));
+        self.errors
+    }
 }
@@ -0,0 +1,208 @@
+// long form explanations of every error, meant for people who are just starting out programming
+// the codes must never change or be reused because they are linked to from outside of the program
+
+pub(crate) struct Explanation {
+    pub(crate) code: &'static str,
+    pub(crate) title: &'static str,
+    pub(crate) explanation: &'static str,
+    // the wrong example must produce this error and the fixed example must run without any errors, which is checked by the tests
+    pub(crate) wrong_example: &'static str,
+    pub(crate) fixed_example: &'static str,
+}
+
+pub(crate) static VAR_UNINITIALIZED: Explanation = Explanation {
+    code: "E0001",
+    title: "variable used before it was given a value",
+    explanation: "A variable was read before anything was stored in it.\n\nA variable that is created without an initial value (like 'var total;' or 'make var total;') is empty until a value is assigned to it. Reading an empty variable is an error because there is nothing to read.\n\nGive the variable a value when it is created, or assign it a value before it is read.",
+    wrong_example: "var total;\nprint total;\n",
+    fixed_example: "var total = 0;\nprint total;\n",
+};
+
+pub(crate) static VAR_DOES_NOT_EXIST: Explanation = Explanation {
+    code: "E0002",
+    title: "variable does not exist",
+    explanation: "A variable was used, but no variable with that name exists at that point in the program.\n\nThis is usually caused by a typo in the name of the variable, by forgetting to create the variable with 'var', or by using a variable outside of the block ('{ ... }') that it was created in. Variables only exist from the statement that creates them until the end of the block that they were created in.",
+    wrong_example: "var count = 1;\nprint cuont;\n",
+    fixed_example: "var count = 1;\nprint count;\n",
+};
+
+pub(crate) static EXPECTED_BOOL: Explanation = Explanation {
+    code: "E0003",
+    title: "condition is not a 'bool'",
+    explanation: "The condition of an 'if' statement or a 'while' loop was something other than 'true' or 'false'.\n\nConditions decide whether or not some code runs, so they have to be either 'true' or 'false'. Other values like numbers or strings are not automatically converted into 'true' or 'false'. Use a comparison like '>' or '==' to turn the value into a 'bool'.",
+    wrong_example: "var x = 3;\nwhile x {\n    x = x - 1;\n}\n",
+    fixed_example: "var x = 3;\nwhile x > 0 {\n    x = x - 1;\n}\n",
+};
+
+pub(crate) static INVALID_TYPE_FOR_SHORT_CIRCUIT_OP: Explanation = Explanation {
+    code: "E0004",
+    title: "logical operator used on something that is not a 'bool'",
+    explanation: "The operators '&&' (and) and '||' (or) only work on 'true' and 'false'.\n\nIn a lot of languages, 'x == 5 || 6' means 'x == 5 || x == 6', but that is not the case here: each side of the operator has to be its own complete condition.",
+    wrong_example: "var x = 5;\nif x || false {\n    print x;\n}\n",
+    fixed_example: "var x = 5;\nif x == 5 || false {\n    print x;\n}\n",
+};
+
+pub(crate) static INVALID_TYPES_FOR_BINARY_OP: Explanation = Explanation {
+    code: "E0005",
+    title: "operator used on values of the wrong types",
+    explanation: "An operator like '+' or '<' was used on values that it does not work with.\n\nMost operators need both of their sides to be the same type: '+' can add two 'int's or join two 'string's together, but it cannot add an 'int' to a 'string'. Values are never automatically converted to a different type.",
+    wrong_example: "var age = 10;\nprint \"age: \" + age;\n",
+    fixed_example: "var age = 10;\nprint \"age: \";\nprint age;\n",
+};
+
+pub(crate) static INVALID_TYPE_FOR_UNARY_OP: Explanation = Explanation {
+    code: "E0006",
+    title: "unary operator used on a value of the wrong type",
+    explanation: "A unary operator (an operator that only has one side, like '-' or '!') was used on a value that it does not work with.\n\n'-' negates numbers and only works on 'int's and 'float's. '!' means \"not\" and only works on 'bool's.",
+    wrong_example: "var done = false;\nprint -done;\n",
+    fixed_example: "var done = false;\nprint !done;\n",
+};
+
+pub(crate) static UNTERMINATED_STRING: Explanation = Explanation {
+    code: "E0007",
+    title: "string is missing its closing quote",
+    explanation: "A string literal was started with '\"' but never ended with another '\"'.\n\nEverything after the opening quote is part of the string until the closing quote, so without one the rest of the program is treated as the contents of the string.",
+    wrong_example: "print \"hello;\n",
+    fixed_example: "print \"hello\";\n",
+};
+
+pub(crate) static BAD_CHARACTER: Explanation = Explanation {
+    code: "E0008",
+    title: "character that is not part of the language",
+    explanation: "The program contains a character that does not mean anything in the language, like '$' or '@'.\n\nThese characters can only appear inside of string literals (between two '\"'s).",
+    wrong_example: "var price = $5;\n",
+    fixed_example: "var price = 5;\n",
+};
+
+pub(crate) static MISSING_SEMICOLON: Explanation = Explanation {
+    code: "E0009",
+    title: "missing ';' at the end of a statement",
+    explanation: "Most statements have to end with a ';'.\n\nThe ';' is how the end of a statement is found, because a statement can be spread across multiple lines and multiple statements can be on one line. The error usually points at the start of the next statement, so the missing ';' is normally at the end of the line before it.",
+    wrong_example: "print 1\nprint 2;\n",
+    fixed_example: "print 1;\nprint 2;\n",
+};
+
+pub(crate) static UNCLOSED_DELIMITER: Explanation = Explanation {
+    code: "E0010",
+    title: "bracket is never closed",
+    explanation: "A '(' or '{' was opened but the matching ')' or '}' could not be found.\n\nEvery opening bracket needs a closing bracket of the same kind. The error points at where the closing bracket was expected and also at the opening bracket that it would close.",
+    wrong_example: "print (1 + 2;\n",
+    fixed_example: "print (1 + 2);\n",
+};
+
+pub(crate) static EXPECTED_BLOCK: Explanation = Explanation {
+    code: "E0011",
+    title: "missing '{' after a condition",
+    explanation: "The code that is run by an 'if' statement, an 'else' or a 'while' loop has to be inside of a block, which is surrounded by '{' and '}', even if it is only one statement long.",
+    wrong_example: "var x = 1;\nif x == 1\n    print x;\n",
+    fixed_example: "var x = 1;\nif x == 1 {\n    print x;\n}\n",
+};
+
+pub(crate) static EXPECTED_VARIABLE_NAME: Explanation = Explanation {
+    code: "E0012",
+    title: "missing variable name",
+    explanation: "A 'var' statement needs the name of the variable that it creates.\n\nVariable names can contain letters, numbers and '_', but they cannot start with a number, and they cannot be a word that already means something in the language (like 'if' or 'while').",
+    wrong_example: "var = 5;\n",
+    fixed_example: "var x = 5;\n",
+};
+
+pub(crate) static EXPECTED_EXPRESSION: Explanation = Explanation {
+    code: "E0013",
+    title: "missing value",
+    explanation: "A value (like a number, a string, a variable or a calculation like '1 + 2') was expected, but something else was found.\n\nThis usually happens when a value was forgotten, for example after '=' or on one side of an operator.",
+    wrong_example: "var x = ;\n",
+    fixed_example: "var x = 0;\n",
+};
+
+pub(crate) static INVALID_ASSIGNMENT_TARGET: Explanation = Explanation {
+    code: "E0014",
+    title: "assigning to something that is not a variable",
+    explanation: "The left side of '=' (or the right side of 'to' in an 'assign' statement) has to be a variable, because that is where the value is stored.\n\nUnlike in math, '=' is not an equation: it does not solve for anything, it just stores the value on the right in the variable on the left.",
+    wrong_example: "var x = 0;\nx + 1 = 2;\n",
+    fixed_example: "var x = 0;\nx = 2 - 1;\n",
+};
+
+pub(crate) static EXPECTED_KEYWORD: Explanation = Explanation {
+    code: "E0015",
+    title: "statement is missing a keyword",
+    explanation: "Some statements are made of multiple keywords, and one of them is missing.\n\nAn 'assign' statement looks like 'assign <value> to <variable>;', and a 'make var' statement looks like 'make var <name>;'.",
+    wrong_example: "var x = 0;\nassign 5 x;\n",
+    fixed_example: "var x = 0;\nassign 5 to x;\n",
+};
+
+pub(crate) static INFINITE_LOOP: Explanation = Explanation {
+    code: "E0016",
+    title: "loop never ends",
+    explanation: "A 'while' loop got back to exactly the same state that it was in at the start of an earlier repeat: every variable had the same value and nothing was printed in between.\n\nA program always does the same thing when it starts from the same state, so the loop would keep repeating the same steps forever and the program would never finish.\n\nThis usually happens when the loop never changes the variables in its condition, for example when the line that counts up is forgotten. Something inside of the loop has to change so that the condition eventually becomes 'false'.",
+    wrong_example: "var i = 0;\nvar total = 0;\nwhile i < 3 {\n    total = total + i;\n}\nprint total;\n",
+    fixed_example: "var i = 0;\nvar total = 0;\nwhile i < 3 {\n    total = total + i;\n    i = i + 1;\n}\nprint total;\n",
+};
+
+pub(crate) static STEP_BUDGET_EXCEEDED: Explanation = Explanation {
+    code: "E0017",
+    title: "program ran for too many steps",
+    explanation: "The program was stopped because it took more steps than it is allowed to (with '--max-steps' on the command line, or the limit of the page).\n\nThis is not always a mistake: a program that does a lot of work can simply need more steps. But most of the time it means that a loop never ends, and because the loop keeps printing or changing a variable, it never gets back to exactly the same state, so it cannot be found as a loop that never ends.\n\nCheck that the condition of every loop eventually becomes 'false'.",
+    wrong_example: "var i = 0;\nwhile i >= 0 {\n    i = i + 1;\n}\n",
+    fixed_example: "var i = 0;\nwhile i < 10 {\n    i = i + 1;\n}\n",
+};
+
+pub(crate) static TIME_BUDGET_EXCEEDED: Explanation = Explanation {
+    code: "E0018",
+    title: "program ran for too long",
+    explanation: "The program was stopped because it ran for longer than it is allowed to (with '--max-time' on the command line).\n\nThis is not always a mistake: a program that does a lot of work can simply need more time. But most of the time it means that a loop never ends, and because the loop keeps printing or changing a variable, it never gets back to exactly the same state, so it cannot be found as a loop that never ends.\n\nCheck that the condition of every loop eventually becomes 'false'.",
+    wrong_example: "var i = 0;\nwhile i >= 0 {\n    print i;\n    i = i + 1;\n}\n",
+    fixed_example: "var i = 0;\nwhile i < 10 {\n    print i;\n    i = i + 1;\n}\n",
+};
+
+pub(crate) static DIVISION_BY_ZERO: Explanation = Explanation {
+    code: "E0019",
+    title: "division by zero",
+    explanation: "An 'int' was divided by 0 with '/', or the remainder of dividing by 0 was taken with '%'.\n\nThere is no number that is the result of dividing by 0, so this is an error instead of giving a wrong answer. Check that the number that is divided by is not 0 before dividing, for example with an 'if' statement.",
+    wrong_example: "var total = 10;\nvar count = 0;\nprint total / count;\n",
+    fixed_example: "var total = 10;\nvar count = 0;\nif count != 0 {\n    print total / count;\n}\n",
+};
+
+pub(crate) static CALL_NOT_SUPPORTED: Explanation = Explanation {
+    code: "E0020",
+    title: "functions cannot be called",
+    explanation: "Something was called like a function, with '(' and ')' after it.\n\nThe language does not have functions yet, so there is nothing that can be called. Write out what the function would do with the statements that exist instead.",
+    wrong_example: "var a = 3;\nvar b = 5;\nprint max(a, b);\n",
+    fixed_example: "var a = 3;\nvar b = 5;\nif a > b {\n    print a;\n} else {\n    print b;\n}\n",
+};
+
+pub(crate) static EXTRANEOUS_INPUT: Explanation = Explanation {
+    code: "E0021",
+    title: "more after the end of the expression",
+    explanation: "A watch expression or the condition of a breakpoint has something after the end of the expression.\n\nWatches and conditions are a single expression, like 'x + 1' or 'i > 3', not statements, so they cannot have a ';' at the end and cannot have two values next to each other without an operator in between them.",
+    wrong_example: "x y",
+    fixed_example: "x + y",
+};
+
+// codes are matched case insensitively so that 'e0001' also works
+pub(crate) fn lookup(code: &str) -> Option<&'static Explanation> {
+    ALL.iter().copied().find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+}
+
+pub(crate) static ALL: &[&Explanation] = &[
+    &VAR_UNINITIALIZED,
+    &VAR_DOES_NOT_EXIST,
+    &EXPECTED_BOOL,
+    &INVALID_TYPE_FOR_SHORT_CIRCUIT_OP,
+    &INVALID_TYPES_FOR_BINARY_OP,
+    &INVALID_TYPE_FOR_UNARY_OP,
+    &UNTERMINATED_STRING,
+    &BAD_CHARACTER,
+    &MISSING_SEMICOLON,
+    &UNCLOSED_DELIMITER,
+    &EXPECTED_BLOCK,
+    &EXPECTED_VARIABLE_NAME,
+    &EXPECTED_EXPRESSION,
+    &INVALID_ASSIGNMENT_TARGET,
+    &EXPECTED_KEYWORD,
+    &INFINITE_LOOP,
+    &STEP_BUDGET_EXCEEDED,
+    &TIME_BUDGET_EXCEEDED,
+    &DIVISION_BY_ZERO,
+    &CALL_NOT_SUPPORTED,
+    &EXTRANEOUS_INPUT,
+];
@@ -0,0 +1,271 @@
+use std::{collections::BTreeSet, rc::Rc};
+
+use crate::{
+    error::{Error, Footnote},
+    source::{File, Span},
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Style {
+    Plain,
+    Header,
+    Gutter,
+    Primary,
+    Secondary,
+    Note,
+    Help,
+}
+
+// one line of rendered output, split up into differently styled segments
+pub(crate) type StyledLine = Vec<(Style, String)>;
+
+// multiline spans that cover more lines than this have the lines in their middle elided
+const MAX_MULTILINE_LINES_SHOWN: usize = 4;
+
+struct Annotation<'a> {
+    style: Style,
+    label: Option<&'a str>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+impl Annotation<'_> {
+    fn is_multiline(&self) -> bool {
+        self.start.0 != self.end.0
+    }
+
+    fn marker(&self) -> char {
+        match self.style {
+            Style::Primary => '^',
+            _ => '-',
+        }
+    }
+
+    fn label_segment(&self) -> Option<(Style, String)> {
+        self.label.map(|label| (self.style, format!(" {label}")))
+    }
+}
+
+pub(crate) fn render_plain(error: &Error) -> String {
+    render(error).into_iter().map(|line| line.into_iter().map(|(_, text)| text).collect::<String>()).collect::<Vec<_>>().join("\n")
+}
+
+pub(crate) fn render(error: &Error) -> Vec<StyledLine> {
+    // group the annotations by the file that they are in so that each file gets its own snippet
+    let mut files: Vec<(&Span, Vec<Annotation>)> = Vec::new();
+    for (span, style, label) in error.span.iter().map(|span| (span, Style::Primary, None)).chain(error.labels.iter().map(|(span, label)| (span, Style::Secondary, Some(label.as_str())))) {
+        let annotation = Annotation { style, label, start: span.start_line_col(), end: span.end_line_col() };
+        match files.iter_mut().find(|(first_span, _)| Rc::ptr_eq(&first_span.file, &span.file)) {
+            Some((_, annotations)) => annotations.push(annotation),
+            None => files.push((span, vec![annotation])),
+        }
+    }
+
+    let replacement_lines = error.footnotes.iter().filter_map(|footnote| match footnote {
+        Footnote::Help { replacement: Some((span, _)), .. } => Some(span.end_line_col().0),
+        _ => None,
+    });
+    let gutter_width = files.iter().flat_map(|(_, annotations)| annotations).map(|annotation| annotation.end.0).chain(replacement_lines).max().unwrap_or(0).to_string().len();
+
+    let header = match error.explanation {
+        Some(explanation) => format!("error[{}]: {}", explanation.code, error.main_message),
+        None => format!("error: {}", error.main_message),
+    };
+    let mut lines = vec![vec![(Style::Header, header)]];
+    for (first_span, annotations) in &files {
+        lines.push(vec![(Style::Gutter, format!("{:gutter_width$}--> ", "")), (Style::Plain, first_span.to_string())]);
+        lines.push(vec![(Style::Gutter, format!("{:gutter_width$} |", ""))]);
+        render_snippet(&first_span.file, annotations, gutter_width, &mut lines);
+    }
+    for footnote in &error.footnotes {
+        match footnote {
+            Footnote::Note(note) => lines.push(vec![(Style::Gutter, format!("{:gutter_width$} = ", "")), (Style::Note, format!("note: {note}"))]),
+            Footnote::Help { message, replacement } => {
+                lines.push(vec![(Style::Gutter, format!("{:gutter_width$} = ", "")), (Style::Help, format!("help: {message}"))]);
+                if let Some((span, replacement)) = replacement {
+                    render_replacement(span, replacement, gutter_width, &mut lines);
+                }
+            }
+        }
+    }
+
+    for line in &mut lines {
+        if let Some((_, last)) = line.last_mut() {
+            last.truncate(last.trim_end().len());
+        }
+    }
+
+    lines
+}
+
+fn render_snippet(file: &File, annotations: &[Annotation], gutter_width: usize, lines: &mut Vec<StyledLine>) {
+    // every multiline annotation gets its own column to the left of the source code
+    let multiline: Vec<&Annotation> = annotations.iter().filter(|annotation| annotation.is_multiline()).collect();
+    let single_line: Vec<&Annotation> = annotations.iter().filter(|annotation| !annotation.is_multiline()).collect();
+    let multiline_gutter_width = multiline.len() * 2;
+
+    let mut shown_lines = BTreeSet::new();
+    for annotation in annotations {
+        if annotation.end.0 - annotation.start.0 < MAX_MULTILINE_LINES_SHOWN {
+            shown_lines.extend(annotation.start.0..=annotation.end.0);
+        } else {
+            shown_lines.extend([annotation.start.0, annotation.start.0 + 1, annotation.end.0 - 1, annotation.end.0]);
+        }
+    }
+
+    let empty_gutter = || (Style::Gutter, format!("{:gutter_width$} | ", ""));
+    let multiline_columns = |open: &[bool], up_to: usize| -> StyledLine {
+        multiline.iter().zip(open).take(up_to).map(|(annotation, open)| if *open { (annotation.style, "| ".to_string()) } else { (Style::Plain, "  ".to_string()) }).collect()
+    };
+
+    let mut last_shown = None;
+    for line_number in shown_lines {
+        if last_shown.is_some_and(|last_shown| line_number > last_shown + 1) {
+            lines.push(vec![(Style::Gutter, "...".to_string())]);
+        }
+        last_shown = Some(line_number);
+
+        // whether or not the vertical line of each multiline annotation is currently being drawn
+        let mut open: Vec<bool> = multiline.iter().map(|annotation| annotation.start.0 < line_number && line_number <= annotation.end.0).collect();
+
+        let contents = file.lines.get(line_number - 1).map(|(_, contents)| contents.replace('\t', " ")).unwrap_or_default();
+        let mut source_line = vec![(Style::Gutter, format!("{line_number:>gutter_width$} | "))];
+        source_line.extend(multiline_columns(&open, multiline.len()));
+        source_line.push((Style::Plain, contents));
+        lines.push(source_line);
+
+        for annotation in single_line.iter().filter(|annotation| annotation.start.0 == line_number) {
+            let mut row = vec![empty_gutter()];
+            row.extend(multiline_columns(&open, multiline.len()));
+            row.push((Style::Plain, " ".repeat(annotation.start.1 - 1)));
+            row.push((annotation.style, annotation.marker().to_string().repeat(std::cmp::max(annotation.end.1 - annotation.start.1, 1))));
+            row.extend(annotation.label_segment());
+            lines.push(row);
+        }
+
+        for (column, annotation) in multiline.iter().enumerate().filter(|(_, annotation)| annotation.start.0 == line_number) {
+            let marker_offset = multiline_gutter_width + annotation.start.1 - 1;
+            let mut row = vec![empty_gutter()];
+            row.extend(multiline_columns(&open, column));
+            row.push((annotation.style, format!(" {}{}", "_".repeat(marker_offset.saturating_sub(column * 2 + 1)), annotation.marker())));
+            lines.push(row);
+            open[column] = true;
+        }
+
+        for (column, annotation) in multiline.iter().enumerate().filter(|(_, annotation)| annotation.end.0 == line_number) {
+            // point at the last character in the span, not the character after it
+            let marker_offset = multiline_gutter_width + std::cmp::max(annotation.end.1, 2) - 2;
+            let mut row = vec![empty_gutter()];
+            row.extend(multiline_columns(&open, column));
+            row.push((annotation.style, format!("|{}{}", "_".repeat(marker_offset.saturating_sub(column * 2 + 1)), annotation.marker())));
+            row.extend(annotation.label_segment());
+            lines.push(row);
+            open[column] = false;
+        }
+    }
+}
+
+// shows what the line will look like once the replacement is applied
+fn render_replacement(span: &Span, replacement: &str, gutter_width: usize, lines: &mut Vec<StyledLine>) {
+    let (line_number, _) = span.start_line_col();
+    let Some((line_range, contents)) = span.file.lines.get(line_number - 1) else { return };
+    // replacements that span multiple lines are not shown because there is no good way to point them out
+    if span.end > line_range.end {
+        return;
+    }
+
+    let before = contents[..span.start - line_range.start].replace('\t', " ");
+    let after = &contents[span.end - line_range.start..];
+    lines.push(vec![(Style::Gutter, format!("{:gutter_width$} |", ""))]);
+    lines.push(vec![(Style::Gutter, format!("{line_number:>gutter_width$} | ")), (Style::Plain, before.clone()), (Style::Help, replacement.to_string()), (Style::Plain, after.to_string())]);
+    lines.push(vec![(Style::Gutter, format!("{:gutter_width$} | ", "")), (Style::Plain, " ".repeat(before.chars().count())), (Style::Help, "~".repeat(std::cmp::max(replacement.chars().count(), 1)))]);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        error::{render::render_plain, Error},
+        source::{File, Span},
+    };
+
+    #[test]
+    fn single_line() {
+        let file = File::new("test.pel".to_string(), "var x = 1 + true;\n".to_string());
+        let error = Error::new(Some(Span::new_from_start_and_end(&file, 10, 11)), "invalid types 'int' and 'bool' to operator '+'".to_string())
+            .with_label(Span::new_from_start_and_end(&file, 12, 16), "this is a 'bool'".to_string());
+        assert_eq!(
+            render_plain(&error),
+            ["error: invalid types 'int' and 'bool' to operator '+'", " --> test.pel:1:11-12", "  |", "1 | var x = 1 + true;", "  |           ^", "  |             ---- this is a 'bool'",].join("\n")
+        );
+    }
+
+    #[test]
+    fn multiline() {
+        let file = File::new("test.pel".to_string(), "while x {\n    print x;\n}\n".to_string());
+        let error =
+            Error::new(Some(Span::new_from_start_and_end(&file, 6, 7)), "expected 'bool', got 'int'".to_string()).with_label(Span::new_from_start_and_end(&file, 8, 24), "loop body".to_string());
+        assert_eq!(
+            render_plain(&error),
+            ["error: expected 'bool', got 'int'", " --> test.pel:1:7-8", "  |", "1 |   while x {", "  |         ^", "  |  _________-", "2 | |     print x;", "3 | | }", "  | |_- loop body",]
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn elided_lines() {
+        let file = File::new("test.pel".to_string(), "{\n1;\n2;\n3;\n4;\n5;\n}".to_string());
+        let error = Error::new(Some(Span::new_from_start_and_end(&file, 0, 18)), "block".to_string());
+        assert_eq!(render_plain(&error), ["error: block", " --> test.pel:(1:1)-(7:2)", "  |", "1 |   {", "  |  _^", "2 | | 1;", "...", "6 | | 5;", "7 | | }", "  | |_^"].join("\n"));
+    }
+
+    #[test]
+    fn notes_and_help() {
+        let file = File::new("test.pel".to_string(), "\"abc".to_string());
+        let error = Error::new(Some(Span::new_from_start_and_end(&file, 0, 4)), "unterminated string literal".to_string())
+            .with_note("strings cannot span multiple lines".to_string())
+            .with_help("add a '\"' at the end of the string".to_string());
+        assert_eq!(
+            render_plain(&error),
+            [
+                "error: unterminated string literal",
+                " --> test.pel:1:1-5",
+                "  |",
+                "1 | \"abc",
+                "  | ^^^^",
+                "  = note: strings cannot span multiple lines",
+                "  = help: add a '\"' at the end of the string",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn suggestion() {
+        let file = File::new("test.pel".to_string(), "var iter = 0;\nprint itr;\n".to_string());
+        let error = Error::new(Some(Span::new_from_start_and_end(&file, 20, 23)), "variable 'itr' does not exist".to_string()).with_suggestion(
+            "a variable with a similar name exists: 'iter'".to_string(),
+            Span::new_from_start_and_end(&file, 20, 23),
+            "iter".to_string(),
+        );
+        assert_eq!(
+            render_plain(&error),
+            [
+                "error: variable 'itr' does not exist",
+                " --> test.pel:2:7-10",
+                "  |",
+                "2 | print itr;",
+                "  |       ^^^",
+                "  = help: a variable with a similar name exists: 'iter'",
+                "  |",
+                "2 | print iter;",
+                "  |       ~~~~",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn no_span() {
+        assert_eq!(render_plain(&Error::new(None, "expected 1 argument".to_string())), "error: expected 1 argument");
+    }
+}
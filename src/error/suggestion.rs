@@ -0,0 +1,69 @@
+// optimal string alignment distance: levenshtein distance where swapping two adjacent characters also only counts as one edit, so that typos like 'whlie' are still close to 'while'
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances[i][j] is the distance between the first i characters of a and the first j characters of b
+    let mut distances: Vec<Vec<usize>> = (0..=a.len())
+        .map(|i| {
+            (0..=b.len())
+                .map(|j| {
+                    if i == 0 {
+                        j
+                    } else if j == 0 {
+                        i
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution_cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1).min(distances[i][j - 1] + 1).min(distances[i - 1][j - 1] + substitution_cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+// finds the candidate that is most likely to be what was meant by the given name, if any of them are close enough
+pub(crate) fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = std::cmp::max(name.chars().count() / 3, 1);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min() // ties are broken alphabetically so that the suggestion does not depend on the order of the candidates
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::suggestion::{closest, edit_distance};
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("while", "while"), 0);
+        assert_eq!(edit_distance("whlie", "while"), 1);
+        assert_eq!(edit_distance("prnt", "print"), 1);
+        assert_eq!(edit_distance("iter", "item"), 1);
+        assert_eq!(edit_distance("", "var"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn closest_candidate() {
+        assert_eq!(closest("whlie", ["if", "else", "while", "var"]), Some("while"));
+        assert_eq!(closest("itr", ["iter", "fizz", "buzz"]), Some("iter"));
+        assert_eq!(closest("fuzz", ["fizz", "buzz"]), Some("buzz"));
+        assert_eq!(closest("output", ["iter", "fizz", "buzz"]), None);
+    }
+}
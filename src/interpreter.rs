@@ -1,3 +1,5 @@
+#[cfg(feature = "web")]
+pub(crate) mod error_screen;
 #[allow(clippy::module_inception)]
 pub(crate) mod interpreter;
 pub(crate) mod lang;
@@ -0,0 +1,81 @@
+use std::rc::Rc;
+
+use crate::{
+    app::graphics::Color,
+    app::{
+        theme::Theme,
+        widgets::{
+            clickable::{Clickable, MouseButton},
+            code_view::{self, code_view},
+            error_view::ErrorView,
+            flex,
+            padding::Padding,
+            Widget,
+        },
+    },
+    error::Error,
+    source::{File, Span},
+};
+
+// the spans of the errors are highlighted in the error color and their labels in the label color
+// the first error is the primary highlight, so that the code view scrolls to it, and the end of the file is used when no error has a span
+pub(crate) fn error_highlights(file: &Rc<File>, errors: &[Error], theme: &Theme) -> ((Span, Color), Vec<(Span, Color)>) {
+    let mut error_spans = errors.iter().filter_map(|error| error.span.clone());
+    let primary_highlight = (error_spans.next().unwrap_or_else(|| file.eof_span()), theme.error);
+    let secondary_highlights = error_spans.map(|span| (span, theme.error)).chain(errors.iter().flat_map(|error| error.labels.iter().map(|(span, _)| (span.clone(), theme.error_label)))).collect();
+    (primary_highlight, secondary_highlights)
+}
+
+// shown instead of the interpreter when the program has errors that prevent it from running
+pub(crate) struct ErrorScreen {
+    file: Rc<File>,
+    errors: Vec<Error>,
+}
+
+impl ErrorScreen {
+    pub(crate) fn new(file: Rc<File>, errors: Vec<Error>) -> ErrorScreen {
+        ErrorScreen { file, errors }
+    }
+
+    pub(crate) fn view(&self, theme: &Theme) -> impl Widget<ErrorScreen> {
+        let (primary_highlight, secondary_highlights) = error_highlights(&self.file, &self.errors, theme);
+
+        let error_list = flex::homogeneous::Flex::new_vertical(
+            self.errors
+                .iter()
+                .map(|error| {
+                    let line = error.span.as_ref().map(|span| span.start_line_col().0);
+                    (
+                        flex::ItemSettings::Fixed,
+                        Clickable::new(
+                            MouseButton::Main,
+                            move |_: &mut ErrorScreen| {
+                                if let Some(line) = line {
+                                    code_view::scroll_to_line(line);
+                                }
+                            },
+                            Padding::all_around(ErrorView::new(error, theme), 5.0),
+                        ),
+                    )
+                })
+                .collect(),
+        );
+
+        flex!(horizontal {
+            code_view: (flex::ItemSettings::Flex(0.5), Padding::all_around(code_view(primary_highlight, secondary_highlights, Vec::new(), theme), 5.0)),
+            errors: (
+                flex::ItemSettings::Flex(0.5),
+                Padding::all_around(
+                    flex!(vertical {
+                        header: (
+                            flex::ItemSettings::Fixed,
+                            theme.label(format!("the program could not be run because it has {} error{}", self.errors.len(), if self.errors.len() == 1 { "" } else { "s" }))
+                        ),
+                        list: (flex::ItemSettings::Flex(1.0), error_list),
+                    }),
+                    5.0
+                )
+            ),
+        })
+    }
+}
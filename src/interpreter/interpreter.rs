@@ -1,126 +1,199 @@
+#[cfg(feature = "web")]
+mod breakpoints;
+#[cfg(feature = "web")]
+mod env_view;
+#[cfg(feature = "web")]
+mod expr_tree_view;
+#[cfg(feature = "web")]
+mod history;
 mod interpreter;
+#[cfg(feature = "web")]
+mod output_view;
+#[cfg(feature = "web")]
+mod step_mode;
+mod trace;
+#[cfg(feature = "web")]
+mod view;
+#[cfg(feature = "web")]
+mod watches;
 
-use std::future::Future;
+#[cfg(feature = "web")]
+use std::{cell::RefCell, rc::Rc};
+use std::{future::Future, pin::Pin};
 
-use genawaiter::sync::Gen;
+use genawaiter::rc::Gen;
 
-use crate::{
-    app::{
-        graphics::{self, Color, Key},
-        widgets::{code_view::code_view, either::Either, flex, label::Label, min_size::MinSize, padding::Padding, responds_to_keyboard::RespondsToKeyboard, Widget},
-    },
-    interpreter::{
-        interpreter::interpreter::{value::ReprValue, InterpretYield, RuntimeError},
-        lang::Stmt,
-    },
+#[cfg(feature = "web")]
+use crate::interpreter::interpreter::interpreter::{Checkpoint, Checkpoints};
+use crate::interpreter::{
+    interpreter::interpreter::{InterpretYield, InterpreterState, RuntimeError},
+    lang::Stmt,
 };
 
-pub(crate) struct Interpreter<'file, F: Future<Output = Result<(), RuntimeError<'file>>>> {
-    last_yield: InterpreterViewState<'file>,
-    generator: Gen<InterpretYield<'file>, (), F>,
+pub(crate) use interpreter::Budget;
+pub(crate) use trace::run_traced;
+#[cfg(feature = "web")]
+pub(crate) use view::{new_interpreter, Interpreter};
+
+// boxed so that the type of the generator can be named
+type InterpreterFuture = Pin<Box<dyn Future<Output = (InterpreterState, Result<(), RuntimeError>)>>>;
+type InterpreterGenerator = Gen<InterpretYield, (), InterpreterFuture>;
+
+fn new_generator(stmts: Vec<Stmt>, budget: Budget) -> InterpreterGenerator {
+    Gen::new(move |co| Box::pin(interpreter::interpret(stmts, budget, co)) as InterpreterFuture)
 }
-enum InterpreterViewState<'file> {
-    NotStarted,
-    AboutToExecute(InterpretYield<'file>),
-    Finished { result: Result<(), RuntimeError<'file>> },
+#[cfg(feature = "web")]
+fn generator_with_checkpoints(stmts: Vec<Stmt>, budget: Budget, checkpoints: Rc<RefCell<Checkpoints>>) -> InterpreterGenerator {
+    Gen::new(move |co| Box::pin(interpreter::interpret_with_checkpoints(stmts, budget, checkpoints, co)) as InterpreterFuture)
+}
+#[cfg(feature = "web")]
+fn generator_from_checkpoint(stmts: Vec<Stmt>, checkpoint: Checkpoint) -> InterpreterGenerator {
+    Gen::new(move |co| Box::pin(interpreter::interpret_from(stmts, checkpoint, co)) as InterpreterFuture)
 }
 
-pub(crate) fn new_interpreter(stmts: Vec<Stmt>) -> Interpreter<impl Future<Output = Result<(), RuntimeError>>> {
-    let gen = Gen::new(move |co| interpreter::interpret(stmts, co));
-    Interpreter { last_yield: InterpreterViewState::NotStarted, generator: gen }
+fn run_to_completion(stmts: Vec<Stmt>, budget: Budget, mut on_step: impl FnMut(&InterpretYield)) -> (InterpreterState, Result<(), RuntimeError>) {
+    let mut generator = new_generator(stmts, budget);
+    loop {
+        match generator.resume() {
+            genawaiter::GeneratorState::Yielded(step) => on_step(&step),
+            genawaiter::GeneratorState::Complete(result) => return result,
+        }
+    }
 }
-impl<'file, F: Future<Output = Result<(), RuntimeError<'file>>> + 'file> Interpreter<'file, F> {
-    pub(crate) fn view(&self) -> impl Widget<Interpreter<'file, F>> {
-        let make_message = |message| Either::new_left(Label::new(message, "sans-serif".to_string(), 15));
-        let widget = match &self.last_yield {
-            InterpreterViewState::NotStarted => make_message("interpreter not started".to_string()),
-            InterpreterViewState::AboutToExecute(InterpretYield { msg, primary_highlight, secondary_highlights, substitutions, state }) => {
-                // TODO: hashmap does not preserve order that variables are created
-                // TODO: padding constant
-                // TODO: adjustable font size
-
-                Either::new_right(Either::new_right(flex!(horizontal {
-                    code_view: (
-                        flex::ItemSettings::Flex(0.3),
-                        Padding::all_around(
-                            code_view((*primary_highlight, Color::rgb(50, 100, 50)), secondary_highlights.clone(), substitutions.clone(), "sans-serif".to_string(), 15, "monospace".to_string(), 15),
-                            5.0
-                        )
-                    ), // TODO: pick better colors
-                    program_output: (flex::ItemSettings::Flex(0.3), Padding::all_around(Label::new(state.program_output.clone(), "monospace".to_string(), 15), 5.0)), // TODO: scrolling, min size, fixed size?, scroll to bottom automatically
-                    env_view: (flex::ItemSettings::Flex(0.2), Padding::all_around(view_env(&state.env), 5.0)),
-                    msg: (flex::ItemSettings::Flex(0.2), Padding::all_around(Label::new(format!("running\n{msg}"), "sans-serif".to_string(), 15), 5.0)),
-                })))
-            }
-            InterpreterViewState::Finished { result: Ok(()) } => make_message("interpreter finished successfully".to_string()),
-            InterpreterViewState::Finished { result: Err(err) } => Either::new_right(Either::new_left(flex!(horizontal {
-                code_view: (
-                    flex::ItemSettings::Flex(0.3),
-                    Padding::all_around(code_view((err.span, Color::rgb(150, 0, 0)), Vec::new(), Vec::new(), "sans-serif".to_string(), 15, "monospace".to_string(), 15), 5.0)
-                ),
-                msg: (flex::ItemSettings::Flex(0.3), Padding::all_around(Label::new(format!("interpreter had error: {}", err.kind), "sans-serif".to_string(), 15), 5.0)),
-            }))),
-        };
-
-        RespondsToKeyboard::<Self, _, _>::new(Key::Space, |interpreter: &mut _| interpreter.step(), widget)
+
+// steps through the whole program without showing any of the steps
+// on_output is called with the output of the program as it is printed instead of all at the end so that programs that never finish still show their output
+pub(crate) fn run_headless(stmts: Vec<Stmt>, budget: Budget, mut on_output: impl FnMut(&str)) -> Result<(), RuntimeError> {
+    let mut output_len = 0;
+    let mut flush_output = |state: &InterpreterState| {
+        for entry in &state.program_output.entries()[output_len..] {
+            on_output(&entry.text);
+        }
+        output_len = state.program_output.len();
+    };
+    let (final_state, result) = run_to_completion(stmts, budget, |step| flush_output(&step.state));
+    flush_output(&final_state);
+    result
+}
+
+// programs for the tests of every part of the interpreter, which have to parse
+#[cfg(test)]
+fn test_program(source: &str) -> (std::rc::Rc<crate::source::File>, Vec<Stmt>) {
+    let file = crate::source::File::new("test.pel".to_string(), source.to_string());
+    let stmts = crate::interpreter::parser::parse_statements(&file).unwrap_or_else(|_| panic!("program should parse"));
+    (file, stmts)
+}
+// the state at every step of the program
+#[cfg(all(test, feature = "web"))]
+fn states_of(source: &str) -> Vec<InterpreterState> {
+    let mut states = Vec::new();
+    let _ = run_to_completion(test_program(source).1, Budget::UNLIMITED, |step| states.push(step.state.clone()));
+    states
+}
+// the state at the last step of the program, which is after the given source has run because a statement is added to the end
+#[cfg(all(test, feature = "web"))]
+fn state_after(source: &str) -> InterpreterState {
+    states_of(&format!("{source}\nprint 0;\n")).pop().expect("program should have steps")
+}
+
+#[cfg(test)]
+mod test {
+    use std::{rc::Rc, time::Duration};
+
+    use crate::{
+        error::{catalog, render::render_plain, Error, Footnote},
+        interpreter::{
+            interpreter::{run_headless, test_program, Budget},
+            parser::{parse_expr, parse_statements},
+        },
+        source::File,
+    };
+
+    fn run(file: &Rc<File>, budget: Budget) -> Result<(), Vec<Error>> {
+        run_headless(parse_statements(file)?, budget, |_| {}).map_err(|err| vec![(&err).into()])
     }
 
-    fn step(&mut self) {
-        match self.last_yield {
-            InterpreterViewState::NotStarted | InterpreterViewState::AboutToExecute { .. } => match self.generator.resume() {
-                genawaiter::GeneratorState::Yielded(step) => self.last_yield = InterpreterViewState::AboutToExecute(step),
-                genawaiter::GeneratorState::Complete(res) => self.last_yield = InterpreterViewState::Finished { result: res },
-            },
+    #[test]
+    fn catalog_examples() {
+        for (index, explanation) in catalog::ALL.iter().enumerate() {
+            assert_eq!(explanation.code, format!("E{:04}", index + 1), "codes should be in order and not have any gaps");
+            // the examples of the budget errors only go over a budget that is small enough to not make the tests slow
+            let budget = match explanation.code {
+                "E0017" => Budget { max_steps: Some(1000), max_time: None },
+                "E0018" => Budget { max_steps: None, max_time: Some(Duration::from_millis(100)) },
+                _ => Budget::UNLIMITED,
+            };
+            // watch expressions and breakpoint conditions are the only places where there can be input after an expression, so those examples are only parsed as an expression
+            let run = |file: &Rc<File>| if explanation.code == "E0021" { parse_expr(file).map(|_| ()) } else { run(file, budget) };
+
+            let wrong = File::new(format!("{} wrong example", explanation.code), explanation.wrong_example.to_string());
+            match run(&wrong) {
+                Ok(()) => panic!("wrong example for {} ran without errors", explanation.code),
+                Err(errors) => assert_eq!(errors[0].explanation.map(|explanation| explanation.code), Some(explanation.code), "wrong example produced:\n{}", render_plain(&errors[0])),
+            }
 
-            InterpreterViewState::Finished { result: _, .. } => {}
+            let fixed = File::new(format!("{} fixed example", explanation.code), explanation.fixed_example.to_string());
+            if let Err(errors) = run(&fixed) {
+                panic!("fixed example for {} has errors:\n{}", explanation.code, errors.iter().map(render_plain).collect::<Vec<_>>().join("\n"));
+            }
         }
     }
-}
 
-fn view_env<Data>(env: &interpreter::Vars) -> impl Widget<Data> {
-    // TODO: var and value side by side in table aligned
-    flex::homogeneous::Flex::new(
-        flex::Direction::Vertical,
-        env.scopes
-            .iter()
-            .flat_map(|env_scope| {
-                env_scope.iter().map(|(var_name, value)| {
-                    (
-                        // TODO: grid widget
-                        flex::ItemSettings::Fixed,
-                        MinSize::new(
-                            flex!(horizontal {
-                                name: (
-                                    flex::ItemSettings::Flex(0.5),
-                                    Padding::new(MinSize::new(Label::new(var_name.to_string(), "sans-serif".to_string(), 15), graphics::Vector2f::new(50.0, 0.0)), 10.0, 5.0, 10.0, 5.0)
-                                ),
-
-                                value: (
-                                    flex::ItemSettings::Flex(0.5),
-                                    Padding::new(
-                                        MinSize::new(
-                                            Label::new(
-                                                match &value.1 {
-                                                    Some(value) => ReprValue(value).to_string(),
-                                                    None => "<uninitialized>".to_string(),
-                                                },
-                                                "sans-serif".to_string(),
-                                                15,
-                                            ),
-                                            graphics::Vector2f::new(50.0, 0.0)
-                                        ),
-                                        10.0,
-                                        5.0,
-                                        10.0,
-                                        5.0
-                                    )
-                                ),
-                            }),
-                            graphics::Vector2f::new(0.0, 25.0),
-                        ),
-                    )
-                })
-            })
-            .collect(),
-    )
+    // the notes of the error that the program stops with
+    fn error_notes(source: &str, budget: Budget) -> Vec<String> {
+        let err = run_headless(test_program(source).1, budget, |_| {}).expect_err("program should have an error");
+        let error: Error = (&err).into();
+        std::iter::once(error.main_message)
+            .chain(error.footnotes.iter().map(|footnote| match footnote {
+                Footnote::Note(note) => note.clone(),
+                Footnote::Help { message, .. } => message.clone(),
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn infinite_loops() {
+        assert_eq!(
+            error_notes("var i = 0;\nvar n = 3;\nwhile i < n {\n    var doubled = i * 2;\n}\n", Budget::UNLIMITED)[..3],
+            [
+                "loop never ends",
+                "the loop got back to exactly the same state that it was in before, so it would keep repeating the same steps forever",
+                "at the start of every repeat of the loop, 'i' is always 0, 'n' is always 3"
+            ]
+        );
+        // a loop that keeps switching between a few states is also found, and the variables that switch are not counted as unchanged
+        assert_eq!(
+            error_notes("var on = false;\nvar count = 1;\nwhile count > 0 {\n    on = !on;\n}\n", Budget::UNLIMITED)[2..4],
+            ["at the start of every repeat of the loop, 'count' is always 1", "'on' change inside of the loop, but keep going back to the same values"]
+        );
+        // printing changes the state, so these can only be stopped by the budget
+        assert_eq!(error_notes("while true {\n    print 1;\n}\n", Budget { max_steps: Some(50), max_time: None })[0], "program did not finish in 50 steps");
+        assert_eq!(error_notes("while true {\n    print 1;\n}\n", Budget { max_steps: None, max_time: Some(Duration::from_millis(10)) })[0], "program did not finish in 0.01 seconds");
+    }
+
+    #[test]
+    fn loops_that_end_are_not_infinite() {
+        let (_, stmts) = test_program("var i = 0;\nvar x = 0;\nwhile i < 100 {\n    x = 1 - x;\n    i = i + 1;\n}\nprint x;\n");
+        let mut output = String::new();
+        assert!(run_headless(stmts, Budget::UNLIMITED, |printed| output.push_str(printed)).is_ok());
+        assert_eq!(output, "0\n");
+    }
+
+    #[test]
+    #[cfg(feature = "web")]
+    fn output_entries() {
+        use crate::interpreter::interpreter::run_to_completion;
+
+        let file = File::new("test.pel".to_string(), "var i = 0;\nwhile i < 2 {\n    print i;\n    i = i + 1;\n}\nprint \"done\";\n".to_string());
+        let mut steps = Vec::new();
+        let (final_state, result) = run_to_completion(parse_statements(&file).unwrap_or_else(|_| panic!("program should parse")), Budget::UNLIMITED, |step| {
+            steps.push((step.msg.clone(), step.primary_highlight.start_line_col().0));
+        });
+        assert!(result.is_ok());
+        // every entry points to the step that printed it and the line of the print statement
+        let output = final_state.program_output.entries();
+        let entries: Vec<_> = output.iter().map(|entry| (entry.text.as_str(), entry.span.start_line_col().0, steps[entry.step].clone())).collect();
+        assert_eq!(entries, [("0\n", 3, ("print value 0".to_string(), 3)), ("1\n", 3, ("print value 1".to_string(), 3)), ("done\n", 6, ("print value \"done\"".to_string(), 6)),]);
+        assert_eq!(final_state.output_text(), "0\n1\ndone\n");
+    }
 }
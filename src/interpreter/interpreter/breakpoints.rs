@@ -0,0 +1,131 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    error::Error,
+    interpreter::{
+        interpreter::interpreter::{evaluate_detached, value::Value, InterpreterState},
+        lang::Expr,
+        parser,
+    },
+    source::File,
+};
+
+// line numbers start at 1
+pub(super) struct Breakpoints {
+    breakpoints: BTreeMap<usize, Breakpoint>,
+}
+pub(super) struct Breakpoint {
+    // kept so that the condition can be shown the way that it was typed
+    pub(super) condition_source: String,
+    condition: Condition,
+}
+enum Condition {
+    Always,
+    Expr(Expr),
+    Invalid(String),
+}
+
+impl Breakpoint {
+    pub(super) fn condition_error(&self) -> Option<&str> {
+        match &self.condition {
+            Condition::Invalid(message) => Some(message),
+            Condition::Always | Condition::Expr(_) => None,
+        }
+    }
+}
+
+impl Breakpoints {
+    pub(super) fn new() -> Breakpoints {
+        Breakpoints { breakpoints: BTreeMap::new() }
+    }
+
+    pub(super) fn lines(&self) -> BTreeSet<usize> {
+        self.breakpoints.keys().copied().collect()
+    }
+    pub(super) fn iter(&self) -> impl Iterator<Item = (usize, &Breakpoint)> {
+        self.breakpoints.iter().map(|(line, breakpoint)| (*line, breakpoint))
+    }
+    pub(super) fn contains(&self, line: usize) -> bool {
+        self.breakpoints.contains_key(&line)
+    }
+
+    pub(super) fn toggle(&mut self, line: usize) {
+        if self.breakpoints.remove(&line).is_none() {
+            self.breakpoints.insert(line, Breakpoint { condition_source: String::new(), condition: Condition::Always });
+        }
+    }
+
+    // an empty condition makes the breakpoint always stop
+    pub(super) fn set_condition(&mut self, line: usize, condition_source: String) {
+        let Some(breakpoint) = self.breakpoints.get_mut(&line) else { return };
+        breakpoint.condition = if condition_source.trim().is_empty() {
+            Condition::Always
+        } else {
+            // the errors only ever point into the condition, so only the message is kept
+            match parser::parse_expr(&File::new(format!("condition of breakpoint on line {line}"), condition_source.clone())) {
+                Ok(expr) => Condition::Expr(expr),
+                Err(errors) => Condition::Invalid(errors.first().map_or_else(|| "invalid condition".to_string(), |error| error.main_message.clone())),
+            }
+        };
+        breakpoint.condition_source = condition_source;
+    }
+
+    // the state is only asked for if the breakpoint has a condition
+    // returns an error message if the condition could not be checked, which should also stop execution so that the problem can be seen
+    pub(super) fn should_stop(&self, line: usize, state: impl FnOnce() -> InterpreterState) -> Result<bool, String> {
+        match self.breakpoints.get(&line).map(|breakpoint| &breakpoint.condition) {
+            None => Ok(false),
+            Some(Condition::Always) => Ok(true),
+            Some(Condition::Invalid(message)) => Err(message.clone()),
+            Some(Condition::Expr(expr)) => match evaluate_detached(&state(), expr.clone()) {
+                Ok(Value::Bool(result)) => Ok(result),
+                Ok(other) => Err(format!("the condition is a '{}', not a 'bool'", other.type_())),
+                Err(err) => Err(Error::from(&err).main_message),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interpreter::interpreter::{breakpoints::Breakpoints, state_after};
+
+    #[test]
+    fn toggle() {
+        let mut breakpoints = Breakpoints::new();
+        breakpoints.toggle(3);
+        breakpoints.toggle(5);
+        breakpoints.toggle(3);
+        assert_eq!(breakpoints.lines().into_iter().collect::<Vec<_>>(), [5]);
+        assert_eq!(breakpoints.should_stop(5, || unreachable!("unconditional breakpoints should not need the state")), Ok(true));
+        assert_eq!(breakpoints.should_stop(3, || unreachable!("lines without breakpoints should not need the state")), Ok(false));
+    }
+
+    #[test]
+    fn conditions() {
+        let mut breakpoints = Breakpoints::new();
+        breakpoints.toggle(1);
+
+        breakpoints.set_condition(1, "x > 2".to_string());
+        assert_eq!(breakpoints.should_stop(1, || state_after("var x = 3;")), Ok(true));
+        assert_eq!(breakpoints.should_stop(1, || state_after("var x = 1;")), Ok(false));
+
+        breakpoints.set_condition(1, "x + 1".to_string());
+        assert_eq!(breakpoints.should_stop(1, || state_after("var x = 3;")), Err("the condition is a 'int', not a 'bool'".to_string()));
+
+        breakpoints.set_condition(1, "y".to_string());
+        assert_eq!(breakpoints.should_stop(1, || state_after("var x = 3;")), Err("variable 'y' does not exist".to_string()));
+
+        // conditions that fail to evaluate stop with the error instead of crashing
+        breakpoints.set_condition(1, "x % 0 == 0".to_string());
+        assert_eq!(breakpoints.should_stop(1, || state_after("var x = 3;")), Err("division by zero".to_string()));
+        breakpoints.set_condition(1, "even(x)".to_string());
+        assert_eq!(breakpoints.should_stop(1, || state_after("var x = 3;")), Err("functions cannot be called".to_string()));
+
+        breakpoints.set_condition(1, "x >".to_string());
+        assert!(breakpoints.iter().next().is_some_and(|(_, breakpoint)| breakpoint.condition_error().is_some()));
+
+        breakpoints.set_condition(1, "  ".to_string());
+        assert_eq!(breakpoints.should_stop(1, || unreachable!("empty conditions should always stop")), Ok(true));
+    }
+}
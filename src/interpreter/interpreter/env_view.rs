@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use crate::{
+    app::{theme::Theme, vdom, widgets::Widget},
+    interpreter::{
+        interpreter::interpreter::{
+            value::{ReprValue, Value},
+            Scope, Vars,
+        },
+        lang::VarName,
+    },
+};
+
+// every scope is shown as a box inside of the box of the scope that it is in, with the variables in the order that they were made
+// variables that were made or changed by the last step flash
+pub(super) struct EnvView {
+    scopes: Vec<ScopeView>,
+    step: usize,
+    theme: Theme,
+}
+struct ScopeView {
+    label: String,
+    vars: Vec<VarView>,
+}
+struct VarView {
+    name: String,
+    // None if the variable is uninitialized
+    value: Option<(String, String)>,
+    // hidden by a variable with the same name in a scope inside of this one
+    shadowed: bool,
+    change: Change,
+}
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Change {
+    Unchanged,
+    New,
+    // the value that the variable had before the step
+    Changed(String),
+}
+
+impl EnvView {
+    // previous is the state before the last step, which is None if there is no step before this one
+    // step is the number of the step that is shown, which is used to restart the flashing on every step
+    pub(super) fn new(env: &Vars, previous: Option<&Vars>, step: usize, theme: &Theme) -> EnvView {
+        EnvView {
+            step,
+            theme: *theme,
+            scopes: env
+                .scopes
+                .iter()
+                .enumerate()
+                .map(|(scope_index, scope)| ScopeView {
+                    label: scope_label(scope),
+                    vars: scope
+                        .vars
+                        .iter()
+                        .map(|(name, (_, value))| VarView {
+                            name: name.0.clone(),
+                            value: value.as_ref().map(|value| (ReprValue(value).to_string(), value.type_().to_string())),
+                            shadowed: env.is_shadowed(scope_index, name),
+                            change: previous.map_or(Change::Unchanged, |previous| change(previous, scope_index, scope, name, value)),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// the variables that were made or changed by the last step, with their new values
+pub(super) fn changed_vars(env: &Vars, previous: &Vars) -> Vec<(String, String)> {
+    env.scopes
+        .iter()
+        .enumerate()
+        .flat_map(|(scope_index, scope)| {
+            scope
+                .vars
+                .iter()
+                .filter(move |(name, (_, value))| change(previous, scope_index, scope, name, value) != Change::Unchanged)
+                .map(|(name, (_, value))| (name.0.clone(), repr_or_uninitialized(value)))
+        })
+        .collect()
+}
+
+fn change(previous: &Vars, scope_index: usize, scope: &Scope, name: &VarName, value: &Option<Value>) -> Change {
+    match previous_value(previous, scope_index, scope, name) {
+        None => Change::New,
+        Some(previous_value) if previous_value == value => Change::Unchanged,
+        Some(previous_value) => Change::Changed(repr_or_uninitialized(previous_value)),
+    }
+}
+
+// the value of the variable in the same scope in the previous state, which is None if the variable or the scope did not exist yet
+fn previous_value<'previous>(previous: &'previous Vars, scope_index: usize, scope: &Scope, name: &VarName) -> Option<&'previous Option<Value>> {
+    let block_range = |scope: &Scope| scope.block.as_ref().map(|block| (block.start, block.end));
+    let previous_scope = previous.scopes.get(scope_index).filter(|previous_scope| block_range(previous_scope) == block_range(scope))?;
+    previous_scope.vars.get(name).map(|(_, value)| value)
+}
+
+fn repr_or_uninitialized(value: &Option<Value>) -> String {
+    value.as_ref().map_or_else(|| "<uninitialized>".to_string(), |value| ReprValue(value).to_string())
+}
+
+// the keyframes are in style.css, and switching between the two names every step makes the browser restart the animation even if the same element changes again
+pub(super) fn flash_css(step: usize) -> String {
+    format!("animation: {} 1s ease-out forwards;", if step.is_multiple_of(2) { "changed-a" } else { "changed-b" })
+}
+
+fn scope_label(scope: &Scope) -> String {
+    match &scope.block {
+        None => "program".to_string(),
+        Some(block) => match (block.start_line_col().0, block.end_line_col().0) {
+            (start, end) if start == end => format!("block on line {start}"),
+            (start, end) => format!("block on lines {start} to {end}"),
+        },
+    }
+}
+
+impl<Data> Widget<Data> for EnvView {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        // the innermost scope is made first so that each scope can be put inside of the one around it
+        let (step, theme) = (self.step, self.theme);
+        let innermost = self.scopes.into_iter().rev().fold(None, |inner, scope| Some(view_scope(&theme, scope, inner, step)));
+        vdom::Element { type_: vdom::ElementType::Div, props: HashMap::new(), event_listeners: Vec::new(), timers: Vec::new(), children: innermost.into_iter().collect() }
+    }
+}
+
+fn element<Data>(type_: vdom::ElementType, style: String, children: Vec<vdom::Node<Data>>) -> vdom::Node<Data> {
+    vdom::Node::Element(vdom::Element { type_, props: std::iter::once(("style", style.into())).collect(), event_listeners: Vec::new(), timers: Vec::new(), children })
+}
+fn text<Data>(style: String, text: String) -> vdom::Node<Data> {
+    element(vdom::ElementType::Span, style, vec![vdom::Node::Text(text)])
+}
+
+fn view_scope<Data>(theme: &Theme, scope: ScopeView, inner: Option<vdom::Node<Data>>, step: usize) -> vdom::Node<Data> {
+    let box_style = format!("border: 1px solid {}; border-radius: 5px; padding: 5px; margin: 5px 0px;", theme.border.to_css_color());
+    let label = element(vdom::ElementType::Div, format!("color: {}; font-size: 0.8em;", theme.muted.to_css_color()), vec![vdom::Node::Text(scope.label)]);
+    let children = std::iter::once(label).chain(scope.vars.into_iter().map(|var| view_var(theme, var, step))).chain(inner).collect();
+    element(vdom::ElementType::Div, box_style, children)
+}
+
+fn view_var<Data>(theme: &Theme, var: VarView, step: usize) -> vdom::Node<Data> {
+    let mut row_style = "display: flex; gap: 10px;".to_string();
+    if var.shadowed {
+        row_style += &format!(" color: {};", theme.muted.to_css_color());
+    }
+    if var.change != Change::Unchanged {
+        row_style += &format!(" {}", flash_css(step));
+    }
+
+    let mut children = vec![text("min-width: 50px;".to_string(), var.name)];
+    if let Change::Changed(old_value) = var.change {
+        children.push(text(format!("font-family: {}; text-decoration: line-through; color: {};", theme.code_font, theme.muted.to_css_color()), old_value));
+    }
+    match var.value {
+        Some((value, type_)) => children.extend([
+            text(format!("font-family: {};", theme.code_font), value),
+            text(format!("font-size: 0.8em; border-radius: 3px; padding: 0px 4px; background-color: {};", theme.badge_background.to_css_color()), type_),
+        ]),
+        None => children.push(text(format!("font-style: italic; color: {};", theme.warning.to_css_color()), "<uninitialized>".to_string())),
+    }
+    element(vdom::ElementType::Div, row_style, children)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        app::theme::Theme,
+        interpreter::interpreter::{
+            env_view::{Change, EnvView},
+            run_to_completion, states_of, test_program, Budget,
+        },
+    };
+
+    #[test]
+    fn scopes_in_order() {
+        let (_, stmts) = test_program("var zebra = 1;\nvar apple;\n{\n    var zebra = \"inner\";\n    print zebra;\n}\n");
+        let mut view = None;
+        let _ = run_to_completion(stmts, Budget::UNLIMITED, |step| {
+            if step.msg.starts_with("print") {
+                view = Some(EnvView::new(&step.state.env, None, 0, &Theme::LIGHT));
+            }
+        });
+        let view = view.expect("print statement should have a step");
+
+        let scopes: Vec<_> = view.scopes.iter().map(|scope| (scope.label.as_str(), scope.vars.iter().map(|var| (var.name.as_str(), var.value.clone(), var.shadowed)).collect::<Vec<_>>())).collect();
+        assert_eq!(
+            scopes,
+            [
+                ("program", vec![("zebra", Some(("1".to_string(), "int".to_string())), true), ("apple", None, false)]),
+                ("block on lines 3 to 6", vec![("zebra", Some(("\"inner\"".to_string(), "string".to_string())), false)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn changes() {
+        let states = states_of("var x = 1;\nvar y;\nx = 2;\n{\n    var x = 3;\n    print x;\n}\n");
+        // the changes between every step and the one before it, of the variables that changed
+        let changes: Vec<Vec<(String, Change)>> = states
+            .windows(2)
+            .map(|pair| {
+                let view = EnvView::new(&pair[1].env, Some(&pair[0].env), 0, &Theme::LIGHT);
+                view.scopes.into_iter().flat_map(|scope| scope.vars).filter(|var| var.change != Change::Unchanged).map(|var| (var.name, var.change)).collect()
+            })
+            .collect();
+        let changes: Vec<_> = changes.into_iter().filter(|changes| !changes.is_empty()).collect();
+        // the inner 'x' is new even though there was already an 'x', because it is in a different scope
+        assert_eq!(
+            changes,
+            [vec![("x".to_string(), Change::New)], vec![("y".to_string(), Change::New)], vec![("x".to_string(), Change::Changed("1".to_string()))], vec![("x".to_string(), Change::New)],]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    app::{theme::Theme, vdom, widgets::Widget},
+    interpreter::lang::{Expr, ExprKind, Stmt, StmtKind},
+    source::{Located, Span},
+};
+
+// the expression of the statement that is running, as a tree where every operation is above its operands
+// parts of the expression that have already been evaluated are collapsed into their values, and the part that is being evaluated is marked
+pub(super) struct ExprTreeView {
+    root: TreeNode,
+    theme: Theme,
+}
+#[derive(PartialEq, Debug)]
+struct TreeNode {
+    // the source code of the operator for operations, and of the whole expression otherwise
+    label: String,
+    state: NodeState,
+    children: Vec<TreeNode>,
+}
+#[derive(PartialEq, Debug)]
+enum NodeState {
+    NotEvaluated,
+    Current,
+    Evaluated(String),
+}
+
+impl ExprTreeView {
+    // None if the step is not inside of a statement that has an expression
+    pub(super) fn new(stmts: &[Stmt], primary_highlight: &Span, substitutions: &[(Span, String)], theme: &Theme) -> Option<ExprTreeView> {
+        current_expr(stmts, primary_highlight).map(|expr| ExprTreeView { root: tree(expr, primary_highlight, substitutions), theme: *theme })
+    }
+}
+
+fn same_range(a: &Span, b: &Span) -> bool {
+    a.start == b.start && a.end == b.end
+}
+fn contains(outer: &Span, inner: &Span) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+fn source_text(span: &Span) -> String {
+    span.file.source[span.start..span.end].to_string()
+}
+
+// the expression of the innermost statement that the highlight is in
+// the steps of if statements and while loops highlight the keyword, which also counts as being in the condition
+fn current_expr<'stmts>(stmts: &'stmts [Stmt], highlight: &Span) -> Option<&'stmts Expr> {
+    let stmt = stmts.iter().find(|stmt| contains(&stmt.span, highlight))?;
+    match &stmt.kind {
+        StmtKind::Block(stmts) => current_expr(stmts, highlight),
+        StmtKind::If(keyword, condition, _, _) | StmtKind::While(keyword, condition, _) if same_range(keyword, highlight) || contains(&condition.span, highlight) => Some(condition),
+        StmtKind::If(_, _, then, else_) => current_expr(std::slice::from_ref(&**then), highlight).or_else(|| current_expr(else_.as_deref().map(std::slice::from_ref).unwrap_or_default(), highlight)),
+        StmtKind::While(_, _, body) => current_expr(std::slice::from_ref(&**body), highlight),
+        StmtKind::Expr(expr) | StmtKind::Print(expr) | StmtKind::Return(expr) | StmtKind::MakeVar(_, Some(expr)) | StmtKind::AssignVar(_, expr) => Some(expr),
+        StmtKind::MakeVar(_, None) => None,
+    }
+}
+
+fn tree(expr: &Expr, highlight: &Span, substitutions: &[(Span, String)]) -> TreeNode {
+    // the newest value is used in case a part of the expression was evaluated more than once
+    if let Some((_, value)) = substitutions.iter().rev().find(|(span, _)| same_range(span, &expr.span)) {
+        return TreeNode { label: source_text(&expr.span), state: NodeState::Evaluated(value.clone()), children: Vec::new() };
+    }
+    let state = |span: &Span| if same_range(span, highlight) { NodeState::Current } else { NodeState::NotEvaluated };
+    let children = |exprs: &[&Expr]| exprs.iter().map(|child| tree(child, highlight, substitutions)).collect();
+    match &expr.kind {
+        // parentheses only change the shape of the tree, which already shows what they do
+        ExprKind::Parenthesized(inner) => tree(inner, highlight, substitutions),
+        ExprKind::Var(_) | ExprKind::Int(_) | ExprKind::Float(_) | ExprKind::String(_) | ExprKind::Bool(_) => {
+            TreeNode { label: source_text(&expr.span), state: state(&expr.span), children: Vec::new() }
+        }
+        ExprKind::Call(callee, arguments) => TreeNode { label: "call".to_string(), state: state(&expr.span), children: children(&std::iter::once(&**callee).chain(arguments).collect::<Vec<_>>()) },
+        ExprKind::ShortCircuitOp(left, Located(operator_span, _), right) | ExprKind::BinaryOp(left, Located(operator_span, _), right) => {
+            TreeNode { label: source_text(operator_span), state: state(operator_span), children: children(&[&**left, &**right]) }
+        }
+        ExprKind::UnaryOp(Located(operator_span, _), operand) => TreeNode { label: source_text(operator_span), state: state(operator_span), children: children(&[&**operand]) },
+    }
+}
+
+impl<Data> Widget<Data> for ExprTreeView {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        let mut root = view_node(&self.theme, self.root);
+        root.props.insert("style", format!("font-family: {};", self.theme.code_font).into());
+        root
+    }
+}
+
+fn element<Data>(type_: vdom::ElementType, style: String, children: Vec<vdom::Node<Data>>) -> vdom::Element<Data> {
+    vdom::Element { type_, props: std::iter::once(("style", style.into())).collect(), event_listeners: Vec::new(), timers: Vec::new(), children }
+}
+
+// every node is a line with the children indented under it
+fn view_node<Data>(theme: &Theme, node: TreeNode) -> vdom::Element<Data> {
+    let line = match node.state {
+        NodeState::NotEvaluated => element(vdom::ElementType::Span, String::new(), vec![vdom::Node::Text(node.label)]),
+        NodeState::Current => {
+            let mut line = element(
+                vdom::ElementType::Span,
+                format!("background-color: {}; color: {}; border-radius: 3px; padding: 0px 4px;", theme.primary_highlight.to_css_color(), Theme::text_color_on(theme.primary_highlight).to_css_color()),
+                vec![vdom::Node::Text(node.label)],
+            );
+            line.props.insert("aria-current", "step".into());
+            line
+        }
+        NodeState::Evaluated(value) => element(
+            vdom::ElementType::Span,
+            String::new(),
+            vec![
+                vdom::Node::Element(element(vdom::ElementType::Span, format!("color: {};", theme.muted.to_css_color()), vec![vdom::Node::Text(format!("{} = ", node.label))])),
+                vdom::Node::Element(element(
+                    vdom::ElementType::Span,
+                    format!("border-radius: 3px; padding: 0px 4px; background-color: {};", theme.badge_background.to_css_color()),
+                    vec![vdom::Node::Text(value)],
+                )),
+            ],
+        ),
+    };
+    let children = element(
+        vdom::ElementType::Div,
+        format!("margin-left: 0.5em; padding-left: 1em; border-left: 1px solid {};", theme.border.to_css_color()),
+        node.children.into_iter().map(|child| vdom::Node::Element(view_node(theme, child))).collect(),
+    );
+    element(vdom::ElementType::Div, String::new(), vec![vdom::Node::Element(line), vdom::Node::Element(children)])
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        app::theme::Theme,
+        interpreter::interpreter::{
+            expr_tree_view::{ExprTreeView, NodeState, TreeNode},
+            run_to_completion, test_program, Budget,
+        },
+    };
+
+    // the tree at every step of the program where there is one, written like "+([1], *(<b>, c))" where [1] was evaluated to 1 and <b> is being evaluated
+    fn trees(source: &str) -> Vec<String> {
+        fn describe(node: &TreeNode) -> String {
+            let label = match &node.state {
+                NodeState::NotEvaluated => node.label.clone(),
+                NodeState::Current => format!("<{}>", node.label),
+                NodeState::Evaluated(value) => format!("[{value}]"),
+            };
+            match &node.children[..] {
+                [] => label,
+                children => format!("{label}({})", children.iter().map(describe).collect::<Vec<_>>().join(", ")),
+            }
+        }
+
+        let (_, stmts) = test_program(source);
+        let mut trees = Vec::new();
+        let _ = run_to_completion(stmts.clone(), Budget::UNLIMITED, |step| {
+            if let Some(view) = ExprTreeView::new(&stmts, &step.primary_highlight, &step.substitutions, &Theme::LIGHT) {
+                trees.push(describe(&view.root));
+            }
+        });
+        trees
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            trees("var a = 1;\nvar b = 2;\nvar c = 3;\nprint a + b * c;\n")[3..],
+            ["+(<a>, *(b, c))", "+([1], *(<b>, c))", "+([1], *([2], <c>))", "+([1], <*>([2], [3]))", "<+>([1], [6])", "[7]"]
+        );
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_eq!(trees("print (1 + 2) * 3;\n"), ["*(<+>([1], [2]), 3)", "<*>([3], [3])", "[9]"]);
+    }
+
+    #[test]
+    fn conditions() {
+        assert_eq!(trees("var x = 1;\nif x < 2 {\n    print !true;\n}\n")[1..], ["<(<x>, 2)", "<<>([1], [2])", "[true]", "<!>([true])", "[false]"]);
+    }
+}
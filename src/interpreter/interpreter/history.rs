@@ -0,0 +1,219 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::interpreter::{
+    interpreter::{
+        generator_from_checkpoint, generator_with_checkpoints,
+        interpreter::{Checkpoints, InterpretYield, InterpreterState, RuntimeError, StepKind},
+        Budget, InterpreterGenerator,
+    },
+    lang::Stmt,
+};
+
+// every step that has been run, so that previous steps can be gone back to
+// every step has a copy of all of the variables of the program (the output is shared between them), so storing all of them would use too much memory for long programs
+// instead, a checkpoint is taken about every snapshot_interval steps and the steps after a checkpoint are recreated by continuing the program from it,
+// which works because a program always takes the same steps every time that it is run
+pub(super) struct History {
+    stmts: Vec<Stmt>,
+    // has already run every step that is in the history
+    generator: InterpreterGenerator,
+    len: usize,
+    // small enough to keep for every step, so that breakpoints and the end of a step mode can be found without recreating steps
+    infos: Vec<StepInfo>,
+    // taken by the generator as it runs, where the steps from one checkpoint up to the next one are a chunk
+    checkpoints: Rc<RefCell<Checkpoints>>,
+    // all of the steps of the most recently used chunk, so that going back one step at a time does not continue from a checkpoint for every step
+    chunk: Option<(usize, Vec<Rc<InterpretYield>>)>,
+    finished: Option<(InterpreterState, Result<(), RuntimeError>)>,
+    // the number of steps that were recreated from checkpoints, which the tests use to check that not too many steps are recreated
+    replayed: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(super) struct StepInfo {
+    pub(super) line: usize,
+    pub(super) kind: StepKind,
+    pub(super) depth: usize,
+}
+
+impl History {
+    pub(super) fn new(stmts: Vec<Stmt>, max_steps: Option<usize>, snapshot_interval: usize) -> History {
+        assert!(snapshot_interval > 0, "snapshot interval must be at least 1");
+        let checkpoints = Rc::new(RefCell::new(Checkpoints::new(snapshot_interval)));
+        History {
+            // the time limit of the budget is never used because the program has to take the same steps every time that it is continued from a checkpoint
+            generator: generator_with_checkpoints(stmts.clone(), Budget { max_steps, max_time: None }, Rc::clone(&checkpoints)),
+            stmts,
+            len: 0,
+            infos: Vec::new(),
+            checkpoints,
+            chunk: None,
+            finished: None,
+            replayed: 0,
+        }
+    }
+
+    pub(super) fn stmts(&self) -> &[Stmt] {
+        &self.stmts
+    }
+
+    // the number of steps that have been run
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(super) fn finished(&self) -> Option<&(InterpreterState, Result<(), RuntimeError>)> {
+        self.finished.as_ref()
+    }
+
+    // returns false if the program has already finished
+    pub(super) fn run_step(&mut self) -> bool {
+        if self.finished.is_some() {
+            return false;
+        }
+
+        match self.generator.resume() {
+            genawaiter::GeneratorState::Yielded(step) => {
+                let step = Rc::new(step);
+                self.infos.push(StepInfo { line: step.primary_highlight.start_line_col().0, kind: step.kind, depth: step.depth() });
+                // the checkpoint before a step is always taken before the step is yielded
+                let chunk_index = self.chunk_index(self.len);
+                let starts_chunk = self.checkpoints.borrow().taken[chunk_index].step == self.len;
+                // the chunk that is being run is kept so that the most recent steps can be gone back to without recreating them
+                // if an older chunk was looked at in the middle of this one, the rest of this one is recreated when it is needed instead
+                match &mut self.chunk {
+                    Some((index, steps)) if *index == chunk_index => steps.push(step),
+                    _ if starts_chunk => self.chunk = Some((chunk_index, vec![step])),
+                    _ => {}
+                }
+                self.len += 1;
+                true
+            }
+            genawaiter::GeneratorState::Complete(result) => {
+                self.finished = Some(result);
+                false
+            }
+        }
+    }
+
+    pub(super) fn info(&self, index: usize) -> Option<StepInfo> {
+        self.infos.get(index).copied()
+    }
+
+    pub(super) fn get(&mut self, index: usize) -> Option<Rc<InterpretYield>> {
+        if index >= self.len {
+            return None;
+        }
+
+        let chunk_index = self.chunk_index(index);
+        if !matches!(&self.chunk, Some((index, _)) if *index == chunk_index) {
+            self.chunk = Some((chunk_index, self.replay_chunk(chunk_index)));
+        }
+        let start = self.checkpoints.borrow().taken[chunk_index].step;
+        self.chunk.as_ref().map(|(_, steps)| Rc::clone(&steps[index - start]))
+    }
+
+    // the index of the last checkpoint at or before the step
+    fn chunk_index(&self, step: usize) -> usize {
+        self.checkpoints.borrow().taken.partition_point(|checkpoint| checkpoint.step <= step) - 1
+    }
+
+    fn replay_chunk(&mut self, chunk_index: usize) -> Vec<Rc<InterpretYield>> {
+        let checkpoints = self.checkpoints.borrow();
+        let checkpoint = &checkpoints.taken[chunk_index];
+        let end = checkpoints.taken.get(chunk_index + 1).map_or(self.len, |next| next.step);
+
+        let mut generator = generator_from_checkpoint(self.stmts.clone(), checkpoint.clone());
+        let steps: Vec<_> = (checkpoint.step..end)
+            .map(|_| match generator.resume() {
+                genawaiter::GeneratorState::Yielded(step) => Rc::new(step),
+                genawaiter::GeneratorState::Complete(_) => panic!("program finished earlier when it was continued from a checkpoint than when it was first run"),
+            })
+            .collect();
+        self.replayed += steps.len();
+        steps
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interpreter::interpreter::{history::History, run_to_completion, test_program, Budget};
+
+    fn history(snapshot_interval: usize) -> History {
+        History::new(test_program("var i = 0;\nwhile i < 4 {\n    print i;\n    i = i + 1;\n}\n").1, None, snapshot_interval)
+    }
+
+    fn messages(history: &mut History, indexes: impl Iterator<Item = usize>) -> Vec<String> {
+        indexes.map(|index| history.get(index).expect("step should be in history").msg.clone()).collect()
+    }
+
+    // the messages of every step of the program, found by running it without going back
+    fn expected_messages() -> Vec<String> {
+        let mut every_step = history(1);
+        while every_step.run_step() {}
+        let len = every_step.len();
+        messages(&mut every_step, 0..len)
+    }
+
+    #[test]
+    fn going_back_matches_going_forward() {
+        let expected = expected_messages();
+        for snapshot_interval in [2, 3, 7, 1000] {
+            let mut history = history(snapshot_interval);
+            while history.run_step() {}
+            assert_eq!(history.len(), expected.len());
+            assert_eq!(messages(&mut history, (0..expected.len()).rev()), expected.iter().rev().cloned().collect::<Vec<_>>());
+            assert_eq!(messages(&mut history, 0..expected.len()), expected);
+        }
+    }
+
+    #[test]
+    fn going_back_while_running() {
+        let expected = expected_messages();
+        let mut history = history(3);
+        for _ in 0..5 {
+            history.run_step();
+        }
+        // looking at an earlier chunk in the middle of running a chunk should not mix up the steps of the chunk that is being run
+        assert_eq!(messages(&mut history, 1..2), expected[1..2]);
+        history.run_step();
+        assert_eq!(messages(&mut history, 3..6), expected[3..6]);
+    }
+
+    #[test]
+    fn continues_from_checkpoints() {
+        // loops and if statements inside of each other, so that checkpoints are taken inside of all of them
+        let source = "var i = 0;\nwhile i < 12 {\n    var j = 0;\n    while j < i {\n        if (i + j) % 3 == 0 {\n            print j;\n        } else {\n            j = j + 1;\n        }\n        j = j + 1;\n    }\n    i = i + 1;\n}\n";
+        let mut expected = Vec::new();
+        let _ = run_to_completion(test_program(source).1, Budget::UNLIMITED, |step| expected.push((step.msg.clone(), step.state.output_text())));
+
+        let snapshot_interval = 10;
+        let mut history = History::new(test_program(source).1, None, snapshot_interval);
+        while history.run_step() {}
+        assert_eq!(history.len(), expected.len());
+        for index in (0..expected.len()).rev() {
+            let replayed = history.replayed;
+            let step = history.get(index).expect("step should be in history");
+            assert_eq!((step.msg.clone(), step.state.output_text()), expected[index]);
+            // only the steps from the checkpoint before the step are run again, and checkpoints are at most one statement further apart than the interval
+            assert!(history.replayed - replayed <= 2 * snapshot_interval, "recreating step {index} ran {} steps", history.replayed - replayed);
+        }
+        // going back through the whole program recreates every step once
+        assert!(history.replayed <= expected.len());
+    }
+
+    #[test]
+    fn only_steps_that_have_run() {
+        let mut history = history(3);
+        assert!(history.get(0).is_none());
+        history.run_step();
+        history.run_step();
+        assert!(history.get(1).is_some());
+        assert!(history.get(2).is_none());
+        assert!(history.finished().is_none());
+
+        while history.run_step() {}
+        assert!(history.finished().is_some_and(|(state, result)| result.is_ok() && state.output_text() == "0\n1\n2\n3\n"));
+        assert!(!history.run_step());
+    }
+}
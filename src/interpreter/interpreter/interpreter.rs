@@ -1,13 +1,27 @@
 pub(super) mod type_;
 pub(super) mod value;
 
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    cell::{Ref, RefCell},
+    collections::{BTreeSet, VecDeque},
+    fmt::Display,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 use async_recursion::async_recursion;
-use genawaiter::sync::Co; // TODO: replace with rc::Co
+use genawaiter::rc::Co;
+#[cfg(feature = "web")]
+use genawaiter::rc::Gen;
+use indexmap::IndexMap;
+use num_bigint::Sign;
 
 use crate::{
     app::graphics::Color,
+    error::{
+        catalog::{self, Explanation},
+        suggestion, Error,
+    },
     interpreter::{
         interpreter::interpreter::{
             type_::Type,
@@ -19,103 +33,453 @@ use crate::{
 };
 
 #[derive(Clone)]
-pub(super) struct InterpreterState<'file> {
-    pub(super) env: Vars<'file>,
-    pub(super) program_output: String,
+pub(super) struct InterpreterState {
+    pub(super) env: Vars,
+    pub(super) program_output: Output,
+    budget: BudgetTracker,
+}
+impl InterpreterState {
+    pub(super) fn new(budget: Budget) -> Self {
+        Self { env: Vars { scopes: Vec::new() }, program_output: Output { log: Rc::new(RefCell::new(Vec::new())), len: 0 }, budget: BudgetTracker::new(budget) }
+    }
+
+    // all of the output of the program, the way that it would be printed to a terminal
+    pub(super) fn output_text(&self) -> String {
+        self.program_output.entries().iter().map(|entry| entry.text.as_str()).collect()
+    }
+}
+
+// everything that the program has printed
+// output is only ever added to the end, so every copy of the state shares one log and only keeps how much of it had been printed, instead of every step having a copy of all of the output
+#[derive(Clone)]
+pub(super) struct Output {
+    log: Rc<RefCell<Vec<OutputEntry>>>,
+    len: usize,
 }
-impl InterpreterState<'_> {
-    pub(super) fn new() -> Self {
-        Self { env: Vars { scopes: Vec::new() }, program_output: String::new() }
+impl Output {
+    fn push(&mut self, entry: OutputEntry) {
+        let mut log = self.log.borrow_mut();
+        // continuing from a checkpoint prints the same output again, which is already in the log
+        if log.len() == self.len {
+            log.push(entry);
+        }
+        self.len += 1;
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(super) fn entries(&self) -> Ref<'_, [OutputEntry]> {
+        Ref::map(self.log.borrow(), |log| &log[..self.len])
     }
 }
 
+// what one print statement printed
 #[derive(Clone)]
-pub(super) struct Vars<'file> {
-    pub(super) scopes: Vec<HashMap<VarName, (Span<'file>, Option<Value>)>>,
+pub(super) struct OutputEntry {
+    // includes the newline at the end
+    pub(super) text: String,
+    // the print statement that printed it
+    #[cfg(feature = "web")]
+    pub(super) span: Span,
+    // the index of the step where it was printed
+    #[cfg(feature = "web")]
+    pub(super) step: usize,
+}
+
+// limits on how long a program can run for, so that a program that never finishes does not run forever
+// the time limit makes the program take different steps depending on how fast it runs, so it should not be used when the program is run more than once (like when going back to earlier steps)
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Budget {
+    pub(crate) max_steps: Option<usize>,
+    // only for running natively (the command line), because Instant panics on wasm where there is no clock
+    pub(crate) max_time: Option<Duration>,
 }
-impl<'file> Vars<'file> {
-    fn lookup(&self, vname: &VarName) -> Option<&(Span<'file>, Option<Value>)> {
+impl Budget {
+    pub(crate) const UNLIMITED: Budget = Budget { max_steps: None, max_time: None };
+}
+
+#[derive(Clone)]
+struct BudgetTracker {
+    budget: Budget,
+    steps: usize,
+    // only measured when there is a time limit, because there is no clock on every platform
+    started: Option<Instant>,
+}
+impl BudgetTracker {
+    fn new(budget: Budget) -> BudgetTracker {
+        BudgetTracker { budget, steps: 0, started: budget.max_time.map(|_| Instant::now()) }
+    }
+
+    fn take_step(&mut self) -> Result<(), RuntimeErrorKind> {
+        self.steps += 1;
+        if let Some(max_steps) = self.budget.max_steps.filter(|max_steps| self.steps > *max_steps) {
+            return Err(RuntimeErrorKind::StepBudgetExceeded(max_steps));
+        }
+        if let (Some(max_time), Some(started)) = (self.budget.max_time, self.started) {
+            if started.elapsed() > max_time {
+                return Err(RuntimeErrorKind::TimeBudgetExceeded(max_time));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Vars {
+    pub(super) scopes: Vec<Scope>,
+}
+#[derive(Clone)]
+pub(super) struct Scope {
+    // the block that the scope is for, which is None for the scope of the whole program
+    #[cfg(feature = "web")]
+    pub(super) block: Option<Span>,
+    // in the order that the variables were made, so that they are always shown in the same order
+    pub(super) vars: IndexMap<VarName, (Span, Option<Value>)>,
+}
+impl Vars {
+    fn lookup(&self, vname: &VarName) -> Option<&(Span, Option<Value>)> {
         for scope in self.scopes.iter().rev() {
-            if let Some(result) = scope.get(vname) {
+            if let Some(result) = scope.vars.get(vname) {
                 return Some(result);
             }
         }
         None
     }
-    fn lookup_mut(&mut self, vname: &VarName) -> Option<&mut (Span<'file>, Option<Value>)> {
+    fn lookup_mut(&mut self, vname: &VarName) -> Option<&mut (Span, Option<Value>)> {
         for scope in self.scopes.iter_mut().rev() {
-            if let Some(result) = scope.get_mut(vname) {
+            if let Some(result) = scope.vars.get_mut(vname) {
                 return Some(result);
             }
         }
         None
     }
 
-    fn start_scope(&mut self) {
-        self.scopes.push(HashMap::new());
+    // the variable in scope whose name is the closest to the given name, for suggesting when a variable does not exist
+    fn similar_name(&self, vname: &VarName) -> Option<VarName> {
+        suggestion::closest(&vname.0, self.scopes.iter().flat_map(|scope| scope.vars.keys()).map(|name| name.0.as_str())).map(|name| VarName(name.to_string()))
+    }
+    // whether the variable in the scope at the index is hidden by a variable with the same name in a scope inside of it
+    pub(super) fn is_shadowed(&self, scope_index: usize, vname: &VarName) -> bool {
+        self.scopes.iter().skip(scope_index + 1).any(|scope| scope.vars.contains_key(vname))
+    }
+    // every variable that can be used with its value, with the outermost scopes first, where variables that are shadowed are not included
+    fn values(&self) -> Vec<(&VarName, &Option<Value>)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .flat_map(|(scope_index, scope)| scope.vars.iter().filter(move |(name, _)| !self.is_shadowed(scope_index, name)).map(|(name, (_, value))| (name, value)))
+            .collect()
+    }
+    #[cfg_attr(not(feature = "web"), allow(unused_variables))]
+    fn start_scope(&mut self, block: Option<Span>) {
+        self.scopes.push(Scope {
+            #[cfg(feature = "web")]
+            block,
+            vars: IndexMap::new(),
+        });
     }
     fn end_scope(&mut self) {
         self.scopes.pop();
     }
 
-    fn define_var(&mut self, vname: VarName, span: Span<'file>, initializer: Option<Value>) {
-        self.scopes.last_mut().expect("define var when there are no scopes to define in").insert(vname, (span, initializer));
+    fn define_var(&mut self, vname: VarName, span: Span, initializer: Option<Value>) {
+        self.scopes.last_mut().expect("define var when there are no scopes to define in").vars.insert(vname, (span, initializer));
     }
 }
 
-pub(super) struct InterpretYield<'file> {
+// expression steps are the small steps that make up a statement, and statement steps are where a statement actually does something
+// this is so that steps can be skipped over without the interpreter having to know which steps are going to be shown
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum StepKind {
+    Expr,
+    Stmt,
+}
+
+pub(super) struct InterpretYield {
+    pub(super) kind: StepKind,
     pub(super) msg: String,
-    pub(super) primary_highlight: Span<'file>,
-    pub(super) secondary_highlights: Vec<(Span<'file>, Color)>,
-    pub(super) substitutions: Vec<(Span<'file>, String)>,
-    pub(super) state: InterpreterState<'file>,
+    pub(super) primary_highlight: Span,
+    pub(super) secondary_highlights: Vec<(Span, Color)>,
+    pub(super) substitutions: Vec<(Span, String)>,
+    pub(super) state: InterpreterState,
 }
 
-pub(crate) struct RuntimeError<'file> {
-    pub(crate) span: Span<'file>,
+#[cfg(feature = "web")]
+impl InterpretYield {
+    // how many blocks the step is inside of, where the whole program counts as a block
+    pub(super) fn depth(&self) -> usize {
+        self.state.env.scopes.len()
+    }
+}
+
+pub(crate) struct RuntimeError {
+    pub(crate) span: Span,
     pub(crate) kind: RuntimeErrorKind,
 }
 pub(crate) enum RuntimeErrorKind {
     VarUninitialized(VarName),
-    VarDoesNotExist(VarName),
+    VarDoesNotExist(VarName, Option<VarName>), // the second name is a similarly named variable that is in scope
     InvalidTypeForShortCircuitOp(ShortCircuitOp, Type),
     InvalidTypesForBinaryOp(BinaryOp, Type, Type),
     InvalidTypeForUnaryOp(UnaryOp, Type),
     ExpectedBool(Type),
+    DivisionByZero,
+    CallNotSupported,
+    InfiniteLoop(RepeatedState),
+    StepBudgetExceeded(usize),
+    TimeBudgetExceeded(Duration),
+}
+// the variables that are in scope at the start of a loop that got back to a state that it was already in
+pub(crate) struct RepeatedState {
+    // the variables with the value that they always have
+    pub(crate) unchanged: Vec<(VarName, String)>,
+    // the variables that change but always come back to the same values
+    pub(crate) changing: Vec<VarName>,
+}
+impl RuntimeErrorKind {
+    fn explanation(&self) -> &'static Explanation {
+        match self {
+            RuntimeErrorKind::VarUninitialized(_) => &catalog::VAR_UNINITIALIZED,
+            RuntimeErrorKind::VarDoesNotExist(_, _) => &catalog::VAR_DOES_NOT_EXIST,
+            RuntimeErrorKind::InvalidTypeForShortCircuitOp(_, _) => &catalog::INVALID_TYPE_FOR_SHORT_CIRCUIT_OP,
+            RuntimeErrorKind::InvalidTypesForBinaryOp(_, _, _) => &catalog::INVALID_TYPES_FOR_BINARY_OP,
+            RuntimeErrorKind::InvalidTypeForUnaryOp(_, _) => &catalog::INVALID_TYPE_FOR_UNARY_OP,
+            RuntimeErrorKind::ExpectedBool(_) => &catalog::EXPECTED_BOOL,
+            RuntimeErrorKind::DivisionByZero => &catalog::DIVISION_BY_ZERO,
+            RuntimeErrorKind::CallNotSupported => &catalog::CALL_NOT_SUPPORTED,
+            RuntimeErrorKind::InfiniteLoop(_) => &catalog::INFINITE_LOOP,
+            RuntimeErrorKind::StepBudgetExceeded(_) => &catalog::STEP_BUDGET_EXCEEDED,
+            RuntimeErrorKind::TimeBudgetExceeded(_) => &catalog::TIME_BUDGET_EXCEEDED,
+        }
+    }
 }
 impl Display for RuntimeErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             RuntimeErrorKind::VarUninitialized(vn) => write!(f, "variable '{}' is uninitialized", vn),
-            RuntimeErrorKind::VarDoesNotExist(vn) => write!(f, "variable '{}' does not exist", vn),
+            RuntimeErrorKind::VarDoesNotExist(vn, _) => write!(f, "variable '{}' does not exist", vn),
             RuntimeErrorKind::InvalidTypeForShortCircuitOp(op, ty) => write!(f, "invalid type '{ty}' to operator logical operator '{op}'"),
             RuntimeErrorKind::InvalidTypesForBinaryOp(op, lty, rty) => write!(f, "invalid types '{lty}' and '{rty}' to operator '{op}'"),
             RuntimeErrorKind::InvalidTypeForUnaryOp(op, ty) => write!(f, "invalid type '{ty}' to unary operator '{op}'"),
             RuntimeErrorKind::ExpectedBool(got_ty) => write!(f, "expected 'bool', got '{got_ty}'"),
+            RuntimeErrorKind::DivisionByZero => write!(f, "division by zero"),
+            RuntimeErrorKind::CallNotSupported => write!(f, "functions cannot be called"),
+            RuntimeErrorKind::InfiniteLoop(_) => write!(f, "loop never ends"),
+            RuntimeErrorKind::StepBudgetExceeded(max_steps) => write!(f, "program did not finish in {max_steps} steps"),
+            RuntimeErrorKind::TimeBudgetExceeded(max_time) => write!(f, "program did not finish in {} seconds", max_time.as_secs_f64()),
+        }
+    }
+}
+
+impl From<&RuntimeError> for Error {
+    fn from(err: &RuntimeError) -> Self {
+        let error = Error::new(Some(err.span.clone()), err.kind.to_string());
+        let error = error.with_code(err.kind.explanation());
+        match &err.kind {
+            RuntimeErrorKind::VarDoesNotExist(_, Some(similar)) => error.with_suggestion(format!("a variable with a similar name exists: '{similar}'"), err.span.clone(), similar.to_string()),
+            RuntimeErrorKind::InfiniteLoop(repeated) => {
+                let mut error = error.with_note("the loop got back to exactly the same state that it was in before, so it would keep repeating the same steps forever".to_string());
+                if !repeated.unchanged.is_empty() {
+                    let unchanged: Vec<String> = repeated.unchanged.iter().map(|(name, value)| format!("'{name}' is always {value}")).collect();
+                    error = error.with_note(format!("at the start of every repeat of the loop, {}", unchanged.join(", ")));
+                }
+                if !repeated.changing.is_empty() {
+                    let changing: Vec<String> = repeated.changing.iter().map(|name| format!("'{name}'")).collect();
+                    error = error.with_note(format!("{} change inside of the loop, but keep going back to the same values", changing.join(", ")));
+                }
+                error.with_help("change a variable that the condition uses inside of the loop so that the condition eventually becomes false".to_string())
+            }
+            RuntimeErrorKind::StepBudgetExceeded(_) | RuntimeErrorKind::TimeBudgetExceeded(_) => error.with_note("the program might be stuck in a loop that never ends".to_string()),
+            _ => error,
         }
     }
 }
 
-type ICo<'file> = Co<InterpretYield<'file>>;
-pub(super) async fn interpret<'file>(stmts: Vec<Stmt<'file>>, co: ICo<'file>) -> Result<(), RuntimeError<'file>> {
-    interpret_statements(&mut InterpreterState::new(), stmts, &co).await
+type ICo = Co<InterpretYield>;
+// the final state is returned so that the output of the last statement is not lost
+pub(super) async fn interpret(stmts: Vec<Stmt>, budget: Budget, co: ICo) -> (InterpreterState, Result<(), RuntimeError>) {
+    let mut state = InterpreterState::new(budget);
+    let mut frames = Frames::new(VecDeque::new());
+    let result = interpret_statements(&mut state, None, stmts, &co, &mut frames).await;
+    (state, result)
+}
+
+#[cfg(feature = "web")]
+pub(super) async fn interpret_with_checkpoints(stmts: Vec<Stmt>, budget: Budget, checkpoints: Rc<RefCell<Checkpoints>>, co: ICo) -> (InterpreterState, Result<(), RuntimeError>) {
+    let mut state = InterpreterState::new(budget);
+    let mut frames = Frames::new(VecDeque::new());
+    frames.checkpoints = Some(checkpoints);
+    let result = interpret_statements(&mut state, None, stmts, &co, &mut frames).await;
+    (state, result)
 }
 
-#[async_recursion]
-async fn interpret_statements<'parent, 'parents: 'parent, 'file>(state: &mut InterpreterState<'file>, stmts: Vec<Stmt<'file>>, co: &ICo<'file>) -> Result<(), RuntimeError<'file>> {
-    state.env.start_scope();
-    for stmt in stmts {
-        interpret_statement(state, stmt, co).await?;
+// continues running the program from a checkpoint that was taken while it was run before, without running any of the steps before the checkpoint
+#[cfg(feature = "web")]
+pub(super) async fn interpret_from(stmts: Vec<Stmt>, checkpoint: Checkpoint, co: ICo) -> (InterpreterState, Result<(), RuntimeError>) {
+    let mut state = checkpoint.state;
+    let mut frames = Frames::new(checkpoint.frames.into());
+    let result = interpret_statements(&mut state, None, stmts, &co, &mut frames).await;
+    (state, result)
+}
+
+// the blocks, if statements and loops that are running, from the outermost to the innermost, which is everything that is needed to continue running the program from the middle of it
+#[derive(Clone)]
+enum Frame {
+    // the index of the statement in the block that is running
+    Block(usize),
+    // whether the first branch is running
+    If(bool),
+    While(RepeatedStateDetector),
+}
+
+// where the program can be continued from, which is either the start of a statement or the start of an iteration of a loop
+// these are the only places where nothing is in the middle of being evaluated, so the frames are enough to continue from them
+#[cfg(feature = "web")]
+#[derive(Clone)]
+pub(super) struct Checkpoint {
+    // the index of the step that comes right after the checkpoint
+    pub(super) step: usize,
+    state: InterpreterState,
+    frames: Vec<Frame>,
+}
+
+// checkpoints that are taken while the program runs, at the first place where one can be taken after every interval steps
+// steps in the middle of a statement cannot have a checkpoint, so they can be a little further apart than the interval
+#[cfg(feature = "web")]
+pub(super) struct Checkpoints {
+    interval: usize,
+    next_at: usize,
+    pub(super) taken: Vec<Checkpoint>,
+}
+#[cfg(feature = "web")]
+impl Checkpoints {
+    pub(super) fn new(interval: usize) -> Checkpoints {
+        Checkpoints { interval, next_at: 0, taken: Vec::new() }
+    }
+}
+
+struct Frames {
+    stack: Vec<Frame>,
+    // the frames of the checkpoint that is being continued from, which are taken from the front as the interpreter goes back into them
+    resume: VecDeque<Frame>,
+    // only the history of the web frontend goes back to earlier steps
+    #[cfg(feature = "web")]
+    checkpoints: Option<Rc<RefCell<Checkpoints>>>,
+}
+impl Frames {
+    fn new(resume: VecDeque<Frame>) -> Frames {
+        Frames {
+            stack: Vec::new(),
+            resume,
+            #[cfg(feature = "web")]
+            checkpoints: None,
+        }
+    }
+
+    #[cfg(feature = "web")]
+    fn checkpoint(&self, state: &InterpreterState) {
+        if let Some(checkpoints) = &self.checkpoints {
+            let mut checkpoints = checkpoints.borrow_mut();
+            if state.budget.steps >= checkpoints.next_at {
+                checkpoints.next_at = state.budget.steps + checkpoints.interval;
+                checkpoints.taken.push(Checkpoint { step: state.budget.steps, state: state.clone(), frames: self.stack.clone() });
+            }
+        }
+    }
+}
+
+// evaluates an expression that is not part of the program (like the condition of a breakpoint) without changing the state of the program
+#[cfg(feature = "web")]
+pub(super) fn evaluate_detached(state: &InterpreterState, expr: Expr) -> Result<Value, RuntimeError> {
+    let mut state = state.clone();
+    // the budget is for the program, which should not run out because of expressions that are not part of it
+    state.budget = BudgetTracker::new(Budget::UNLIMITED);
+    let mut generator = Gen::new(|co| async move { interpret_expr(&mut state, &Vec::new(), expr, &co).await });
+    loop {
+        if let genawaiter::GeneratorState::Complete(result) = generator.resume() {
+            return result;
+        }
+    }
+}
+
+// every step goes through here so that the budget is checked at every step
+async fn yield_step(step: InterpretYield, state: &mut InterpreterState, co: &ICo) -> Result<(), RuntimeError> {
+    let span = step.primary_highlight.clone();
+    co.yield_(step).await;
+    state.budget.take_step().map_err(|kind| RuntimeError { span, kind })
+}
+
+// finds loops that can never end because the state of the program at the start of an iteration is exactly the same as at the start of an earlier iteration
+// the program always does the same thing from the same state, so it would repeat the iterations in between forever
+// only one earlier state is kept, which is replaced at iterations that are powers of 2 (brent's algorithm), so that a repeat of any length is found without keeping every iteration
+#[derive(Clone)]
+struct RepeatedStateDetector {
+    iteration: usize,
+    saved: Option<InterpreterState>,
+    previous: Option<Vars>,
+    // the variables that have changed since the saved state
+    changed: BTreeSet<String>,
+}
+impl RepeatedStateDetector {
+    fn new() -> RepeatedStateDetector {
+        RepeatedStateDetector { iteration: 0, saved: None, previous: None, changed: BTreeSet::new() }
+    }
+
+    fn check(&mut self, state: &InterpreterState) -> Option<RepeatedState> {
+        if let Some(previous) = &self.previous {
+            self.changed.extend(state.env.values().into_iter().filter(|(name, value)| previous.lookup(name).map(|(_, previous_value)| previous_value) != Some(value)).map(|(name, _)| name.0.clone()));
+        }
+        // the output only ever grows, so the state cannot be the same if something was printed
+        let repeated = self.saved.as_ref().is_some_and(|saved| saved.program_output.len() == state.program_output.len() && saved.env.values() == state.env.values());
+        if repeated {
+            let (changing, unchanged): (Vec<_>, Vec<_>) = state.env.values().into_iter().partition(|(name, _)| self.changed.contains(&name.0));
+            return Some(RepeatedState {
+                unchanged: unchanged.into_iter().map(|(name, value)| (name.clone(), value.as_ref().map_or_else(|| "uninitialized".to_string(), |value| ReprValue(value).to_string()))).collect(),
+                changing: changing.into_iter().map(|(name, _)| name.clone()).collect(),
+            });
+        }
+
+        self.iteration += 1;
+        if self.iteration.is_power_of_two() {
+            self.saved = Some(state.clone());
+            self.changed.clear();
+        }
+        self.previous = Some(state.env.clone());
+        None
+    }
+}
+
+#[async_recursion(?Send)]
+async fn interpret_statements<'parent, 'parents: 'parent>(state: &mut InterpreterState, block: Option<Span>, stmts: Vec<Stmt>, co: &ICo, frames: &mut Frames) -> Result<(), RuntimeError> {
+    // when continuing from a checkpoint, the scope is already in the state
+    let start = match frames.resume.pop_front() {
+        Some(Frame::Block(start)) => start,
+        Some(_) => unreachable!("checkpoint should have a block frame for every block"),
+        None => {
+            state.env.start_scope(block);
+            0
+        }
+    };
+    let frame_index = frames.stack.len();
+    frames.stack.push(Frame::Block(start));
+    for (index, stmt) in stmts.into_iter().enumerate().skip(start) {
+        frames.stack[frame_index] = Frame::Block(index);
+        #[cfg(feature = "web")]
+        frames.checkpoint(state);
+        interpret_statement(state, stmt, co, frames).await?;
     }
+    frames.stack.pop();
     state.env.end_scope();
 
     Ok(())
 }
 
-#[async_recursion]
-async fn interpret_statement<'parent, 'parents: 'parent, 'file>(state: &mut InterpreterState<'file>, stmt: Stmt<'file>, co: &ICo<'file>) -> Result<(), RuntimeError<'file>> {
+#[async_recursion(?Send)]
+async fn interpret_statement<'parent, 'parents: 'parent>(state: &mut InterpreterState, stmt: Stmt, co: &ICo, frames: &mut Frames) -> Result<(), RuntimeError> {
     match stmt.kind {
-        StmtKind::Block(stmts) => interpret_statements(state, stmts, co).await,
+        StmtKind::Block(stmts) => interpret_statements(state, Some(stmt.span), stmts, co, frames).await,
 
         StmtKind::Expr(e) => {
             interpret_expr(state, &Vec::new(), e, co).await?;
@@ -123,141 +487,211 @@ async fn interpret_statement<'parent, 'parents: 'parent, 'file>(state: &mut Inte
         }
 
         StmtKind::Print(v) => {
-            let v_span = v.span;
+            let v_span = v.span.clone();
             let v = interpret_expr(state, &Vec::new(), v, co).await?;
-            co.yield_(InterpretYield {
-                msg: format!("print value {}", ReprValue(&v)),
-                primary_highlight: stmt.span,
-                secondary_highlights: Vec::new(),
-                state: state.clone(),
-                substitutions: vec![(v_span, ReprValue(&v).to_string())],
-            })
-            .await;
-            state.program_output += &DisplayValue(&v).to_string();
-            state.program_output += "\n";
+            yield_step(
+                InterpretYield {
+                    kind: StepKind::Stmt,
+                    msg: format!("print value {}", ReprValue(&v)),
+                    primary_highlight: stmt.span.clone(),
+                    secondary_highlights: Vec::new(),
+                    state: state.clone(),
+                    substitutions: vec![(v_span, ReprValue(&v).to_string())],
+                },
+                state,
+                co,
+            )
+            .await?;
+            state.program_output.push(OutputEntry {
+                text: format!("{}\n", DisplayValue(&v)),
+                #[cfg(feature = "web")]
+                span: stmt.span.clone(),
+                // the step has already been counted by the time that the output is printed
+                #[cfg(feature = "web")]
+                step: state.budget.steps - 1,
+            });
             Ok(())
         }
 
         StmtKind::Return(_) => todo!(),
 
         StmtKind::MakeVar(vname, None) => {
-            co.yield_(InterpretYield {
-                msg: format!("make uninitialized variable '{vname}'"),
-                primary_highlight: stmt.span,
-                secondary_highlights: Vec::new(),
-                substitutions: Vec::new(),
-                state: state.clone(),
-            })
-            .await;
+            yield_step(
+                InterpretYield {
+                    kind: StepKind::Stmt,
+                    msg: format!("make uninitialized variable '{vname}'"),
+                    primary_highlight: stmt.span.clone(),
+                    secondary_highlights: Vec::new(),
+                    substitutions: Vec::new(),
+                    state: state.clone(),
+                },
+                state,
+                co,
+            )
+            .await?;
             state.env.define_var(vname.clone(), stmt.span, None);
             Ok(())
         }
 
         StmtKind::MakeVar(vname, Some(initializer)) => {
-            let initializer_span = initializer.span;
+            let initializer_span = initializer.span.clone();
             let initializer = interpret_expr(state, &Vec::new(), initializer, co).await?;
-            co.yield_(InterpretYield {
-                msg: format!("make variable '{vname}' with initializer {}", ReprValue(&initializer)),
-                primary_highlight: stmt.span,
-                secondary_highlights: Vec::new(),
-                substitutions: vec![(initializer_span, ReprValue(&initializer).to_string())],
-                state: state.clone(),
-            })
-            .await;
+            yield_step(
+                InterpretYield {
+                    kind: StepKind::Stmt,
+                    msg: format!("make variable '{vname}' with initializer {}", ReprValue(&initializer)),
+                    primary_highlight: stmt.span.clone(),
+                    secondary_highlights: Vec::new(),
+                    substitutions: vec![(initializer_span, ReprValue(&initializer).to_string())],
+                    state: state.clone(),
+                },
+                state,
+                co,
+            )
+            .await?;
             state.env.define_var(vname.clone(), stmt.span, Some(initializer));
             Ok(())
         }
 
-        StmtKind::AssignVar(var, v) => {
-            let v_span = v.span;
+        StmtKind::AssignVar(Located(var_span, var), v) => {
+            let v_span = v.span.clone();
             let v = interpret_expr(state, &Vec::new(), v, co).await?;
-            co.yield_(InterpretYield {
-                msg: format!("assign variable '{var}' with value {}", ReprValue(&v)),
-                primary_highlight: stmt.span,
-                secondary_highlights: Vec::new(),
-                substitutions: vec![(v_span, ReprValue(&v).to_string())],
-                state: state.clone(),
-            })
-            .await;
+            yield_step(
+                InterpretYield {
+                    kind: StepKind::Stmt,
+                    msg: format!("assign variable '{var}' with value {}", ReprValue(&v)),
+                    primary_highlight: stmt.span.clone(),
+                    secondary_highlights: Vec::new(),
+                    substitutions: vec![(v_span, ReprValue(&v).to_string())],
+                    state: state.clone(),
+                },
+                state,
+                co,
+            )
+            .await?;
             match state.env.lookup_mut(&var) {
                 Some(v_place) => {
                     v_place.1 = Some(v);
                     Ok(())
                 }
-                None => Err(RuntimeError { span: stmt.span, kind: RuntimeErrorKind::VarDoesNotExist(var) }),
+                None => {
+                    let similar = state.env.similar_name(&var);
+                    Err(RuntimeError { span: var_span, kind: RuntimeErrorKind::VarDoesNotExist(var, similar) })
+                }
             }
         }
 
+        StmtKind::If(_, _, t, f) if !frames.resume.is_empty() => {
+            let Some(Frame::If(then)) = frames.resume.pop_front() else { unreachable!("checkpoint should have an if frame for every if statement") };
+            interpret_branch(state, then, if then { Some(*t) } else { f.map(|f| *f) }, co, frames).await
+        }
         StmtKind::If(if_span, cond, t, f) => {
-            let cond_span = cond.span;
+            let cond_span = cond.span.clone();
             let cond = interpret_expr(state, &Vec::new(), cond, co).await?;
-            co.yield_(InterpretYield {
-                msg: "check condition".to_string(),
-                primary_highlight: if_span,
-                secondary_highlights: Vec::new(),
-                substitutions: vec![(cond_span, ReprValue(&cond).to_string())],
-                state: state.clone(),
-            })
-            .await;
+            yield_step(
+                InterpretYield {
+                    kind: StepKind::Stmt,
+                    msg: "check condition".to_string(),
+                    primary_highlight: if_span.clone(),
+                    secondary_highlights: Vec::new(),
+                    substitutions: vec![(cond_span.clone(), ReprValue(&cond).to_string())],
+                    state: state.clone(),
+                },
+                state,
+                co,
+            )
+            .await?;
             match cond {
-                Value::Bool(true) => interpret_statement(state, *t, co).await,
-                Value::Bool(false) => {
-                    if let Some(f) = f {
-                        interpret_statement(state, *f, co).await?;
-                    }
-                    Ok(())
-                }
+                Value::Bool(true) => interpret_branch(state, true, Some(*t), co, frames).await,
+                Value::Bool(false) => interpret_branch(state, false, f.map(|f| *f), co, frames).await,
                 cond => Err(RuntimeError { span: cond_span, kind: RuntimeErrorKind::ExpectedBool(cond.type_()) }),
             }
         }
 
-        StmtKind::While(while_span, cond_ast, body) => loop {
-            let cond_value = interpret_expr(state, &Vec::new(), cond_ast.clone(), co).await?;
-            co.yield_(InterpretYield {
-                msg: "check condition".to_string(),
-                primary_highlight: while_span,
-                secondary_highlights: Vec::new(),
-                substitutions: vec![(cond_ast.span, ReprValue(&cond_value).to_string())],
-                state: state.clone(),
-            })
-            .await;
-            match cond_value {
-                Value::Bool(true) => {}
-                Value::Bool(false) => break Ok(()),
-                _ => break Err(RuntimeError { span: cond_ast.span, kind: RuntimeErrorKind::ExpectedBool(cond_value.type_()) }),
-            }
-
-            interpret_statement(state, (*body).clone(), co).await?;
-        },
+        StmtKind::While(while_span, cond_ast, body) => {
+            // a checkpoint in a loop is either at the start of an iteration, after the state was already checked for repeats, or somewhere inside of the body
+            let (detector, mut check_repeats, mut run_body) = match frames.resume.pop_front() {
+                Some(Frame::While(detector)) => (detector, !frames.resume.is_empty(), !frames.resume.is_empty()),
+                Some(_) => unreachable!("checkpoint should have a while frame for every while loop"),
+                None => (RepeatedStateDetector::new(), true, false),
+            };
+            let frame_index = frames.stack.len();
+            frames.stack.push(Frame::While(detector));
+            let result = loop {
+                if std::mem::take(&mut run_body) {
+                    interpret_statement(state, (*body).clone(), co, frames).await?;
+                }
+                if std::mem::replace(&mut check_repeats, true) {
+                    let Frame::While(detector) = &mut frames.stack[frame_index] else { unreachable!("frame of the loop should be a while frame") };
+                    if let Some(repeated) = detector.check(state) {
+                        break Err(RuntimeError { span: while_span, kind: RuntimeErrorKind::InfiniteLoop(repeated) });
+                    }
+                    #[cfg(feature = "web")]
+                    frames.checkpoint(state);
+                }
+                let cond_value = interpret_expr(state, &Vec::new(), cond_ast.clone(), co).await?;
+                yield_step(
+                    InterpretYield {
+                        kind: StepKind::Stmt,
+                        msg: "check condition".to_string(),
+                        primary_highlight: while_span.clone(),
+                        secondary_highlights: Vec::new(),
+                        substitutions: vec![(cond_ast.span.clone(), ReprValue(&cond_value).to_string())],
+                        state: state.clone(),
+                    },
+                    state,
+                    co,
+                )
+                .await?;
+                match cond_value {
+                    Value::Bool(true) => run_body = true,
+                    Value::Bool(false) => break Ok(()),
+                    _ => break Err(RuntimeError { span: cond_ast.span, kind: RuntimeErrorKind::ExpectedBool(cond_value.type_()) }),
+                }
+            };
+            frames.stack.pop();
+            result
+        }
     }
 }
 
-fn add_substitution<'file>(substitutions: &[(Span<'file>, String)], (sp, thing): (Span<'file>, impl ToString)) -> Vec<(Span<'file>, String)> {
+async fn interpret_branch(state: &mut InterpreterState, then: bool, branch: Option<Stmt>, co: &ICo, frames: &mut Frames) -> Result<(), RuntimeError> {
+    let Some(branch) = branch else { return Ok(()) };
+    frames.stack.push(Frame::If(then));
+    interpret_statement(state, branch, co, frames).await?;
+    frames.stack.pop();
+    Ok(())
+}
+
+fn add_substitution(substitutions: &[(Span, String)], (sp, thing): (Span, impl ToString)) -> Vec<(Span, String)> {
     let mut new_substitutions = substitutions.to_vec();
     new_substitutions.push((sp, thing.to_string()));
     new_substitutions
 }
-#[async_recursion]
-async fn interpret_expr<'file: 'async_recursion, 'parent, 'parents>(
-    state: &mut InterpreterState<'file>,
-    substitutions: &Vec<(Span<'file>, String)>,
-    e: Expr<'file>,
-    co: &ICo<'file>,
-) -> Result<Value, RuntimeError<'file>> {
+#[async_recursion(?Send)]
+async fn interpret_expr<'parent, 'parents>(state: &mut InterpreterState, substitutions: &Vec<(Span, String)>, e: Expr, co: &ICo) -> Result<Value, RuntimeError> {
     match e.kind {
         ExprKind::Var(vname) => {
-            co.yield_(InterpretYield {
-                msg: format!("read variable '{vname}'"),
-                primary_highlight: e.span,
-                secondary_highlights: Vec::new(),
-                state: state.clone(),
-                substitutions: substitutions.clone(),
-            })
-            .await;
+            yield_step(
+                InterpretYield {
+                    kind: StepKind::Expr,
+                    msg: format!("read variable '{vname}'"),
+                    primary_highlight: e.span.clone(),
+                    secondary_highlights: Vec::new(),
+                    state: state.clone(),
+                    substitutions: substitutions.clone(),
+                },
+                state,
+                co,
+            )
+            .await?;
             match state.env.lookup(&vname) {
                 Some((_, Some(v))) => Ok(v.clone()),
                 Some((_, None)) => Err(RuntimeError { span: e.span, kind: RuntimeErrorKind::VarUninitialized(vname) }),
-                None => Err(RuntimeError { span: e.span, kind: RuntimeErrorKind::VarDoesNotExist(vname) }),
+                None => {
+                    let similar = state.env.similar_name(&vname);
+                    Err(RuntimeError { span: e.span, kind: RuntimeErrorKind::VarDoesNotExist(vname, similar) })
+                }
             }
         }
         ExprKind::Int(i) => Ok(Value::Int(i)),
@@ -265,12 +699,11 @@ async fn interpret_expr<'file: 'async_recursion, 'parent, 'parents>(
         ExprKind::String(s) => Ok(Value::String(s)),
         ExprKind::Bool(b) => Ok(Value::Bool(b)),
         ExprKind::Parenthesized(e) => Ok(interpret_expr(state, substitutions, *e, co).await?),
-        ExprKind::Call(_, _) => {
-            todo!()
-        }
+        // there are no functions yet, so nothing can be called
+        ExprKind::Call(_, _) => Err(RuntimeError { span: e.span, kind: RuntimeErrorKind::CallNotSupported }),
         ExprKind::ShortCircuitOp(left, Located(_, op), right) => {
-            let left_span = left.span;
-            let right_span = right.span;
+            let left_span = left.span.clone();
+            let right_span = right.span.clone();
             match op {
                 ShortCircuitOp::Or => match interpret_expr(state, substitutions, *left, co).await? {
                     Value::Bool(true) => Ok(Value::Bool(true)),
@@ -291,8 +724,8 @@ async fn interpret_expr<'file: 'async_recursion, 'parent, 'parents>(
             }
         }
         ExprKind::BinaryOp(left, Located(op_span, op), right) => {
-            let left_span = left.span;
-            let right_span = right.span;
+            let left_span = left.span.clone();
+            let right_span = right.span.clone();
 
             let left = interpret_expr(state, substitutions, *left, co).await?;
             let subs_with_left = add_substitution(substitutions, (left_span, ReprValue(&left)));
@@ -311,14 +744,19 @@ async fn interpret_expr<'file: 'async_recursion, 'parent, 'parents>(
                     };
                 }
 
-            co.yield_(InterpretYield {
-                msg: format!("evaluate operation '{}'", op),
-                primary_highlight: op_span,
-                secondary_highlights: Vec::new(),
-                substitutions: subs_with_right,
-                state: state.clone(),
-            })
-            .await;
+            yield_step(
+                InterpretYield {
+                    kind: StepKind::Expr,
+                    msg: format!("evaluate operation '{}'", op),
+                    primary_highlight: op_span.clone(),
+                    secondary_highlights: Vec::new(),
+                    substitutions: subs_with_right,
+                    state: state.clone(),
+                },
+                state,
+                co,
+            )
+            .await?;
             match op {
                 BinaryOp::Equal => comparison!(==),
                 BinaryOp::NotEqual => comparison!(!=),
@@ -344,11 +782,13 @@ async fn interpret_expr<'file: 'async_recursion, 'parent, 'parents>(
                     (left, right) => Err(RuntimeError { span: op_span, kind: RuntimeErrorKind::InvalidTypesForBinaryOp(op, left.type_(), right.type_()) }),
                 },
                 BinaryOp::Divide => match (left, right) {
+                    (Value::Int(_), Value::Int(i2)) if i2.sign() == Sign::NoSign => Err(RuntimeError { span: op_span, kind: RuntimeErrorKind::DivisionByZero }),
                     (Value::Int(i1), Value::Int(i2)) => Ok(Value::Int(i1 / i2)),
                     (Value::Float(f1), Value::Float(f2)) => Ok(Value::Float(f1 / f2)),
                     (left, right) => Err(RuntimeError { span: op_span, kind: RuntimeErrorKind::InvalidTypesForBinaryOp(op, left.type_(), right.type_()) }),
                 },
                 BinaryOp::Modulo => match (left, right) {
+                    (Value::Int(_), Value::Int(i2)) if i2.sign() == Sign::NoSign => Err(RuntimeError { span: op_span, kind: RuntimeErrorKind::DivisionByZero }),
                     (Value::Int(i1), Value::Int(i2)) => Ok(Value::Int(i1 % i2)),
                     (Value::Float(f1), Value::Float(f2)) => Ok(Value::Float(f1 % f2)),
                     (left, right) => Err(RuntimeError { span: op_span, kind: RuntimeErrorKind::InvalidTypesForBinaryOp(op, left.type_(), right.type_()) }),
@@ -356,16 +796,21 @@ async fn interpret_expr<'file: 'async_recursion, 'parent, 'parents>(
             }
         }
         ExprKind::UnaryOp(Located(operator_span, operator), operand) => {
-            let operand_span = operand.span;
+            let operand_span = operand.span.clone();
             let operand = interpret_expr(state, substitutions, *operand, co).await?;
-            co.yield_(InterpretYield {
-                msg: format!("evaluate operation '{}'", operator),
-                primary_highlight: operator_span,
-                secondary_highlights: Vec::new(),
-                substitutions: add_substitution(substitutions, (operand_span, ReprValue(&operand))),
-                state: state.clone(),
-            })
-            .await;
+            yield_step(
+                InterpretYield {
+                    kind: StepKind::Expr,
+                    msg: format!("evaluate operation '{}'", operator),
+                    primary_highlight: operator_span.clone(),
+                    secondary_highlights: Vec::new(),
+                    substitutions: add_substitution(substitutions, (operand_span, ReprValue(&operand))),
+                    state: state.clone(),
+                },
+                state,
+                co,
+            )
+            .await?;
             match operator {
                 UnaryOp::NumericNegate => match operand {
                     Value::Int(i) => Ok(Value::Int(-i)),
@@ -4,7 +4,7 @@ use num_bigint::BigInt;
 
 use crate::interpreter::interpreter::interpreter::type_::Type;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub(crate) enum Value {
     Int(BigInt),
     Float(f64),
@@ -0,0 +1,114 @@
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+
+use crate::{
+    app::{theme::Theme, vdom, widgets::Widget},
+    interpreter::interpreter::{env_view::flash_css, interpreter::OutputEntry},
+    source::Span,
+};
+
+// the output of the program, with one line for every print, where the output that was printed by the last step flashes
+// hovering over a line calls on_hover with the print statement that printed it (and with None when the mouse leaves), and clicking on it calls on_click with the step that printed it
+// the lines can also be focused with the keyboard, which is the same as hovering, and pressing enter on one is the same as clicking
+pub(super) struct OutputView<Data> {
+    entries: Vec<EntryView>,
+    step: usize,
+    theme: Theme,
+    on_hover: Rc<dyn Fn(&mut Data, Option<Span>)>,
+    on_click: Rc<dyn Fn(&mut Data, usize)>,
+}
+struct EntryView {
+    text: String,
+    span: Span,
+    step: usize,
+    new: bool,
+}
+
+impl<Data> OutputView<Data> {
+    // previous_len is the number of entries before the last step, which is None if there is no step before this one
+    pub(super) fn new(
+        output: &[OutputEntry],
+        previous_len: Option<usize>,
+        step: usize,
+        theme: &Theme,
+        on_hover: impl Fn(&mut Data, Option<Span>) + 'static,
+        on_click: impl Fn(&mut Data, usize) + 'static,
+    ) -> OutputView<Data> {
+        let previous_len = previous_len.unwrap_or(output.len());
+        OutputView {
+            entries: output
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| EntryView {
+                    // the newline is left out because every entry is on its own line already
+                    text: entry.text.strip_suffix('\n').unwrap_or(&entry.text).to_string(),
+                    span: entry.span.clone(),
+                    step: entry.step,
+                    new: index >= previous_len,
+                })
+                .collect(),
+            step,
+            theme: *theme,
+            on_hover: Rc::new(on_hover),
+            on_click: Rc::new(on_click),
+        }
+    }
+}
+
+fn element<Data>(type_: vdom::ElementType, style: String, event_listeners: Vec<(&'static str, Box<dyn Fn(wasm_bindgen::JsValue, &mut Data)>)>, children: Vec<vdom::Node<Data>>) -> vdom::Element<Data> {
+    vdom::Element { type_, props: std::iter::once(("style", style.into())).collect(), event_listeners, timers: Vec::new(), children }
+}
+
+impl<Data: 'static> Widget<Data> for OutputView<Data> {
+    fn to_vdom(self) -> vdom::Element<Data> {
+        let step = self.step;
+        let lines = self
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let mut style = format!("cursor: pointer; border-bottom: 1px solid {};", self.theme.border.to_css_color());
+                if entry.new {
+                    style += &format!(" {}", flash_css(step));
+                }
+                let span = entry.span;
+                let on_hover = |hovered: Option<Span>| {
+                    let on_hover = Rc::clone(&self.on_hover);
+                    Box::new(move |_, data: &mut Data| on_hover(data, hovered.clone())) as Box<dyn Fn(wasm_bindgen::JsValue, &mut Data)>
+                };
+                let (on_click, on_enter) = (Rc::clone(&self.on_click), Rc::clone(&self.on_click));
+                let listeners: Vec<(&'static str, Box<dyn Fn(wasm_bindgen::JsValue, &mut Data)>)> = vec![
+                    ("mouseenter", on_hover(Some(span.clone()))),
+                    ("mouseleave", on_hover(None)),
+                    ("focus", on_hover(Some(span))),
+                    ("blur", on_hover(None)),
+                    ("click", Box::new(move |_, data| on_click(data, entry.step))),
+                    (
+                        "keydown",
+                        Box::new(move |event, data| {
+                            let event = event.dyn_into::<web_sys::KeyboardEvent>().expect("keydown should not recieve event data that is not KeyboardEvent");
+                            if event.key() == "Enter" {
+                                event.stop_propagation();
+                                on_enter(data, entry.step);
+                            }
+                        }),
+                    ),
+                ];
+                let label = format!("{}, printed by step {}", entry.text, entry.step + 1);
+                let mut line = element(vdom::ElementType::Div, style, listeners, vec![vdom::Node::Text(entry.text)]);
+                line.props.insert("tabIndex", 0.into());
+                line.props.insert("aria-label", label.into());
+                vdom::Node::Element(line)
+            })
+            .collect();
+
+        // the lines are put in a box inside of a reversed column, which makes the browser keep the pane scrolled to the bottom as lines are added unless it has been scrolled up
+        let lines = element(vdom::ElementType::Pre, format!("margin: 0px; font-family: {};", self.theme.code_font), Vec::new(), lines);
+        element(
+            vdom::ElementType::Div,
+            "display: flex; flex-direction: column-reverse; overflow-y: auto; max-height: 70vh; min-height: 100px;".to_string(),
+            Vec::new(),
+            vec![vdom::Node::Element(lines)],
+        )
+    }
+}
@@ -0,0 +1,112 @@
+use crate::interpreter::interpreter::{history::StepInfo, interpreter::StepKind};
+
+// how far stepping forward goes
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(super) enum StepMode {
+    // every step, including the ones inside of expressions
+    Expression,
+    // only the steps where a statement does something
+    Statement,
+    // the first step on a different line
+    Line,
+    // the first step outside of the current block, which is also the end of the current iteration of a loop because the condition is checked outside of the body
+    OutOfBlock,
+}
+
+impl StepMode {
+    pub(super) const ALL: [StepMode; 4] = [StepMode::Expression, StepMode::Statement, StepMode::Line, StepMode::OutOfBlock];
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            StepMode::Expression => "expression",
+            StepMode::Statement => "statement",
+            StepMode::Line => "line",
+            StepMode::OutOfBlock => "end of block",
+        }
+    }
+
+    // from is the step that stepping started at, which is None if the program has not started
+    pub(super) fn stops_at(self, from: Option<StepInfo>, to: StepInfo) -> bool {
+        match (self, from) {
+            (StepMode::Expression, _) => true,
+            (StepMode::Statement, _) => to.kind == StepKind::Stmt,
+            (StepMode::Line | StepMode::OutOfBlock, None) => true,
+            (StepMode::Line, Some(from)) => to.line != from.line,
+            (StepMode::OutOfBlock, Some(from)) => to.depth < from.depth,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interpreter::interpreter::{history::History, step_mode::StepMode, test_program};
+
+    fn history() -> History {
+        let mut history = History::new(test_program("var i = 0;\nwhile i < 2 {\n    print i + 1;\n    i = i + 1;\n}\nprint i;\n").1, None, 1000);
+        while history.run_step() {}
+        history
+    }
+
+    // the messages of the steps that stepping with the mode stops at, starting from before the first step
+    fn stops(mode: StepMode) -> Vec<String> {
+        let mut history = history();
+        let mut stops = Vec::new();
+        let mut from = None;
+        for index in 0..history.len() {
+            let info = history.info(index).expect("step should be in history");
+            if mode.stops_at(from, info) {
+                stops.push(history.get(index).expect("step should be in history").msg.clone());
+                from = Some(info);
+            }
+        }
+        stops
+    }
+
+    #[test]
+    fn statement() {
+        assert_eq!(
+            stops(StepMode::Statement),
+            [
+                "make variable 'i' with initializer 0",
+                "check condition",
+                "print value 1",
+                "assign variable 'i' with value 1",
+                "check condition",
+                "print value 2",
+                "assign variable 'i' with value 2",
+                "check condition",
+                "print value 2",
+            ]
+        );
+        assert_eq!(stops(StepMode::Expression).len(), history().len());
+    }
+
+    #[test]
+    fn line() {
+        assert_eq!(
+            stops(StepMode::Line),
+            [
+                "make variable 'i' with initializer 0",
+                "read variable 'i'",
+                "read variable 'i'",
+                "read variable 'i'",
+                "read variable 'i'",
+                "read variable 'i'",
+                "read variable 'i'",
+                "read variable 'i'",
+                "read variable 'i'",
+            ]
+        );
+    }
+
+    #[test]
+    fn out_of_block() {
+        let mut history = history();
+        let in_body = (0..history.len()).find(|index| history.info(*index).is_some_and(|info| info.line == 3)).expect("loop body should run");
+        let from = history.info(in_body);
+        let out = (in_body + 1..history.len()).find(|index| StepMode::OutOfBlock.stops_at(from, history.info(*index).expect("step should be in history"))).expect("loop should finish");
+        // leaving the body of the loop stops at the start of checking the condition for the next iteration
+        let step = history.get(out).expect("step should be in history");
+        assert_eq!((step.msg.as_str(), step.primary_highlight.start_line_col().0), ("read variable 'i'", 2));
+    }
+}
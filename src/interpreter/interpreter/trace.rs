@@ -0,0 +1,177 @@
+// a trace is a record of every step that the interpreter takes, written as json lines (one json object per line)
+// the structs here are separate from the ones that the interpreter uses so that the format stays the same when the interpreter changes
+// any change to the format that could break something that reads traces must increase FORMAT_VERSION
+
+use serde::Serialize;
+
+use crate::{
+    error::Error,
+    interpreter::{
+        interpreter::{
+            interpreter::{value::ReprValue, InterpretYield, InterpreterState, RuntimeError, StepKind},
+            run_to_completion, Budget,
+        },
+        lang::Stmt,
+    },
+    source::Span,
+};
+
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Record {
+    Header {
+        version: u32,
+    },
+    Step {
+        step: usize,
+        level: Level,
+        message: String,
+        span: TraceSpan,
+        secondary_spans: Vec<TraceSpan>,
+        substitutions: Vec<Substitution>,
+        // the outermost scope is first, and the variables in a scope are in the order that they were made
+        env: Vec<Vec<Var>>,
+        output: String,
+    },
+    Finished {
+        steps: usize,
+        output: String,
+        error: Option<TraceError>,
+    },
+}
+
+// whether a step is part of evaluating an expression or is where a statement does something
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Level {
+    Expression,
+    Statement,
+}
+
+// start and end are byte offsets into the source, lines and columns start at 1
+#[derive(Serialize)]
+struct TraceSpan {
+    start: usize,
+    end: usize,
+    start_line: usize,
+    start_col: usize,
+    end_line: usize,
+    end_col: usize,
+}
+#[derive(Serialize)]
+struct Substitution {
+    span: TraceSpan,
+    text: String,
+}
+// type and value are null if the variable is uninitialized
+#[derive(Serialize)]
+struct Var {
+    name: String,
+    #[serde(rename = "type")]
+    type_: Option<String>,
+    value: Option<String>,
+}
+#[derive(Serialize)]
+struct TraceError {
+    code: Option<&'static str>,
+    message: String,
+    span: Option<TraceSpan>,
+}
+
+impl From<&Span> for TraceSpan {
+    fn from(span: &Span) -> Self {
+        let (start_line, start_col) = span.start_line_col();
+        let (end_line, end_col) = span.end_line_col();
+        TraceSpan { start: span.start, end: span.end, start_line, start_col, end_line, end_col }
+    }
+}
+
+fn env(state: &InterpreterState) -> Vec<Vec<Var>> {
+    state
+        .env
+        .scopes
+        .iter()
+        .map(|scope| {
+            scope
+                .vars
+                .iter()
+                .map(|(name, (_, value))| Var { name: name.0.clone(), type_: value.as_ref().map(|value| value.type_().to_string()), value: value.as_ref().map(|value| ReprValue(value).to_string()) })
+                .collect()
+        })
+        .collect()
+}
+
+fn step_record(step: usize, yield_: &InterpretYield) -> Record {
+    Record::Step {
+        step,
+        level: match yield_.kind {
+            StepKind::Expr => Level::Expression,
+            StepKind::Stmt => Level::Statement,
+        },
+        message: yield_.msg.clone(),
+        span: (&yield_.primary_highlight).into(),
+        secondary_spans: yield_.secondary_highlights.iter().map(|(span, _)| span.into()).collect(),
+        substitutions: yield_.substitutions.iter().map(|(span, text)| Substitution { span: span.into(), text: text.clone() }).collect(),
+        env: env(&yield_.state),
+        output: yield_.state.output_text(),
+    }
+}
+
+fn finished_record(steps: usize, final_state: &InterpreterState, result: &Result<(), RuntimeError>) -> Record {
+    let error = result.as_ref().err().map(|err| {
+        let error: Error = err.into();
+        TraceError { code: error.explanation.map(|explanation| explanation.code), message: error.main_message, span: error.span.as_ref().map(Into::into) }
+    });
+    Record::Finished { steps, output: final_state.output_text(), error }
+}
+
+// runs the whole program, calling write_line with every line of the trace (without the newline)
+pub(crate) fn run_traced(stmts: Vec<Stmt>, budget: Budget, mut write_line: impl FnMut(&str)) -> Result<(), RuntimeError> {
+    let mut write_record = |record: Record| write_line(&serde_json::to_string(&record).expect("trace records should always be serializable"));
+
+    write_record(Record::Header { version: FORMAT_VERSION });
+    let mut steps = 0;
+    let (final_state, result) = run_to_completion(stmts, budget, |yield_| {
+        write_record(step_record(steps, yield_));
+        steps += 1;
+    });
+    write_record(finished_record(steps, &final_state, &result));
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interpreter::interpreter::{run_traced, test_program, Budget};
+
+    fn trace(source: &str) -> Vec<serde_json::Value> {
+        let mut lines = Vec::new();
+        let _ = run_traced(test_program(source).1, Budget::UNLIMITED, |line| {
+            lines.push(serde_json::from_str(line).expect("trace line should be valid json"));
+        });
+        lines
+    }
+
+    #[test]
+    fn format() {
+        let lines = trace("var x = 1;\nprint x;\n");
+        assert_eq!(lines.first(), Some(&serde_json::json!({ "kind": "header", "version": 1 })));
+        assert_eq!(lines.last(), Some(&serde_json::json!({ "kind": "finished", "steps": lines.len() - 2, "output": "1\n", "error": null })));
+
+        let print_step = lines.iter().find(|line| line["message"] == "print value 1").expect("print statement should have a step");
+        assert_eq!(print_step["level"], "statement");
+        assert!(lines.iter().any(|line| line["message"] == "read variable 'x'" && line["level"] == "expression"));
+        assert_eq!(print_step["span"], serde_json::json!({ "start": 11, "end": 19, "start_line": 2, "start_col": 1, "end_line": 2, "end_col": 9 }));
+        assert_eq!(print_step["env"], serde_json::json!([[{ "name": "x", "type": "int", "value": "1" }]]));
+    }
+
+    #[test]
+    fn error() {
+        let lines = trace("var x;\nprint x;\n");
+        let finished = lines.last().expect("trace should not be empty");
+        assert_eq!(finished["error"]["code"], "E0001");
+        assert_eq!(finished["error"]["message"], "variable 'x' is uninitialized");
+        assert_eq!(finished["error"]["span"]["start"], 13);
+    }
+}
@@ -0,0 +1,532 @@
+use std::rc::Rc;
+
+use crate::{
+    app::{
+        graphics::Color,
+        theme::Theme,
+        widgets::{
+            aria::{Aria, LiveRegion},
+            clickable::{Clickable, MouseButton},
+            code_view::code_view_with_breakpoints,
+            css_class::CssClass,
+            either::Either,
+            error_view::ErrorView,
+            flex,
+            padding::Padding,
+            slider::Slider,
+            text_input::TextInput,
+            timer::Timer,
+            Widget,
+        },
+    },
+    interpreter::{
+        interpreter::{
+            breakpoints::Breakpoints,
+            env_view::{changed_vars, EnvView},
+            expr_tree_view::ExprTreeView,
+            history::History,
+            interpreter::{InterpretYield, InterpreterState},
+            output_view::OutputView,
+            step_mode::StepMode,
+            watches::Watches,
+        },
+        lang::Stmt,
+    },
+    source::{File, Span},
+};
+
+// steps are run ahead of time up to this many so that the scrubber can cover the whole run of most programs
+const RUN_AHEAD_STEPS: usize = 10_000;
+// continuing and stepping give up after this many steps so that a program that never stops does not freeze the page
+const MAX_RUN_STEPS: usize = 100_000;
+const DEFAULT_PLAY_SPEED: usize = 4;
+const MAX_PLAY_SPEED: usize = 20;
+
+pub(crate) struct Interpreter {
+    file: Rc<File>,
+    history: History,
+    // 0 is before the first step, n is the nth step, and one past the last step is after the program has finished
+    position: usize,
+    shown: Shown,
+    breakpoints: Breakpoints,
+    watches: Watches,
+    step_mode: StepMode,
+    playing: bool,
+    // in steps per second
+    play_speed: usize,
+    // why continuing or stepping stopped, if it was not because of a breakpoint or the end of a step
+    status: Option<String>,
+    // the print statement that printed the output that the mouse is over
+    hovered_output: Option<Span>,
+    // animations end as soon as they start, which is also done if the browser asks for less motion
+    reduce_motion: bool,
+}
+enum Shown {
+    NotStarted,
+    // the previous step is kept so that what the step changed can be shown
+    AboutToExecute { step: Rc<InterpretYield>, previous: Option<Rc<InterpretYield>> },
+    Finished,
+}
+
+pub(crate) fn new_interpreter(file: Rc<File>, stmts: Vec<Stmt>, max_steps: Option<usize>, snapshot_interval: usize) -> Interpreter {
+    let mut history = History::new(stmts, max_steps, snapshot_interval);
+    while history.len() < RUN_AHEAD_STEPS && history.run_step() {}
+    Interpreter {
+        file,
+        history,
+        position: 0,
+        shown: Shown::NotStarted,
+        breakpoints: Breakpoints::new(),
+        watches: Watches::new(),
+        step_mode: StepMode::Statement,
+        playing: false,
+        play_speed: DEFAULT_PLAY_SPEED,
+        status: None,
+        hovered_output: None,
+        reduce_motion: false,
+    }
+}
+impl Interpreter {
+    pub(crate) fn view(&self, theme: &Theme) -> impl Widget<Interpreter> {
+        let code_view = |primary_highlight: (Span, Color), secondary_highlights: Vec<(Span, Color)>, substitutions: Vec<(Span, String)>| {
+            Aria::new(
+                "region",
+                "code".to_string(),
+                code_view_with_breakpoints(
+                    primary_highlight,
+                    secondary_highlights.into_iter().chain(self.hovered_output.clone().map(|span| (span, theme.secondary_highlight))).collect::<Vec<_>>(),
+                    substitutions,
+                    theme,
+                    self.breakpoints.lines(),
+                    |interpreter: &mut Interpreter, line| interpreter.breakpoints.toggle(line),
+                ),
+            )
+        };
+        let output_view = |output, previous_len| {
+            Aria::new(
+                "region",
+                "program output".to_string(),
+                OutputView::new(
+                    output,
+                    previous_len,
+                    self.position,
+                    theme,
+                    |interpreter: &mut Interpreter, span| interpreter.hovered_output = span,
+                    |interpreter: &mut Interpreter, step| interpreter.go_to(step + 1),
+                ),
+            )
+        };
+        let widget = match &self.shown {
+            Shown::NotStarted => Either::new_right(Either::new_right(Either::new_right(flex!(horizontal {
+                code_view: (flex::ItemSettings::Flex(0.6), Padding::all_around(code_view((self.file.eof_span(), theme.primary_highlight), Vec::new(), Vec::new()), 5.0)),
+                msg: (flex::ItemSettings::Flex(0.4), Padding::all_around(theme.label("interpreter not started\nclick on a line number to add a breakpoint".to_string()), 5.0)),
+            })))),
+            Shown::AboutToExecute { step, previous } => {
+                let InterpretYield { kind: _, msg, primary_highlight, secondary_highlights, substitutions, state } = &**step;
+                let previous_state = previous.as_ref().map(|previous| &previous.state);
+                // TODO: padding constant
+
+                Either::new_right(Either::new_right(Either::new_left(flex!(horizontal {
+                    code_view: (
+                        flex::ItemSettings::Flex(0.3),
+                        Padding::all_around(code_view((primary_highlight.clone(), theme.primary_highlight), secondary_highlights.clone(), substitutions.clone()), 5.0)
+                    ),
+                    program_output: (
+                        flex::ItemSettings::Flex(0.3),
+                        Padding::all_around(output_view(&state.program_output.entries(), previous_state.map(|previous| previous.program_output.len())), 5.0)
+                    ),
+                    env_view: (
+                        flex::ItemSettings::Flex(0.2),
+                        Padding::all_around(Aria::new("region", "variables".to_string(), EnvView::new(&state.env, previous_state.map(|previous| &previous.env), self.position, theme)), 5.0)
+                    ),
+                    msg: (
+                        flex::ItemSettings::Flex(0.2),
+                        Padding::all_around(
+                            flex!(vertical {
+                                msg: (flex::ItemSettings::Fixed, theme.label(format!("running\n{msg}"))),
+                                expr_tree: (
+                                    flex::ItemSettings::Fixed,
+                                    match ExprTreeView::new(self.history.stmts(), primary_highlight, substitutions, theme) {
+                                        Some(expr_tree) => Either::new_left(Aria::new("region", "expression tree".to_string(), expr_tree)),
+                                        None => Either::new_right(theme.label(String::new())),
+                                    }
+                                ),
+                            }),
+                            5.0
+                        )
+                    ),
+                }))))
+            }
+            Shown::Finished => match self.history.finished() {
+                Some((final_state, Ok(()))) => Either::new_left(flex!(horizontal {
+                    code_view: (flex::ItemSettings::Flex(0.3), Padding::all_around(code_view((self.file.eof_span(), theme.primary_highlight), Vec::new(), Vec::new()), 5.0)),
+                    program_output: (flex::ItemSettings::Flex(0.3), Padding::all_around(output_view(&final_state.program_output.entries(), None), 5.0)),
+                    msg: (flex::ItemSettings::Flex(0.4), Padding::all_around(theme.label("interpreter finished successfully".to_string()), 5.0)),
+                })),
+                Some((final_state, Err(err))) => Either::new_right(Either::new_left(flex!(horizontal {
+                    code_view: (flex::ItemSettings::Flex(0.3), Padding::all_around(code_view((err.span.clone(), theme.error), Vec::new(), Vec::new()), 5.0)),
+                    program_output: (flex::ItemSettings::Flex(0.3), Padding::all_around(output_view(&final_state.program_output.entries(), None), 5.0)),
+                    msg: (flex::ItemSettings::Flex(0.4), Padding::all_around(ErrorView::new(&err.into(), theme), 5.0)),
+                }))),
+                None => unreachable!("finished is only shown once the program has finished"),
+            },
+        };
+
+        let controls = flex!(horizontal {
+            continue_: (
+                flex::ItemSettings::Fixed,
+                Clickable::new(MouseButton::Main, |interpreter: &mut Interpreter| interpreter.continue_to_breakpoint(), Padding::all_around(theme.label("continue".to_string()), 5.0))
+            ),
+            play: (
+                flex::ItemSettings::Fixed,
+                Clickable::new(
+                    MouseButton::Main,
+                    |interpreter: &mut Interpreter| interpreter.toggle_playing(),
+                    Padding::all_around(theme.label(if self.playing { "pause" } else { "play" }.to_string()), 5.0)
+                )
+            ),
+            play_speed: (
+                flex::ItemSettings::Fixed,
+                Aria::label("play speed".to_string(), Slider::new(1, MAX_PLAY_SPEED, self.play_speed, |interpreter: &mut Interpreter, speed| interpreter.play_speed = speed))
+            ),
+            play_speed_label: (flex::ItemSettings::Fixed, Padding::all_around(theme.label(format!("{} steps per second", self.play_speed)), 5.0)),
+            step_mode: (flex::ItemSettings::Fixed, self.view_step_modes(theme)),
+            reduce_motion: (
+                flex::ItemSettings::Fixed,
+                Clickable::new(
+                    MouseButton::Main,
+                    |interpreter: &mut Interpreter| interpreter.reduce_motion = !interpreter.reduce_motion,
+                    Padding::all_around(theme.label(if self.reduce_motion { "[reduce motion]" } else { "reduce motion" }.to_string()), 5.0)
+                )
+            ),
+            scrubber: (
+                flex::ItemSettings::Flex(1.0),
+                Aria::label("step".to_string(), Slider::new(0, self.last_position(), self.position, |interpreter: &mut Interpreter, position| interpreter.go_to(position)))
+            ),
+            position: (flex::ItemSettings::Fixed, Padding::all_around(theme.label(format!("step {} of {}", self.position, self.last_position())), 5.0)),
+        });
+
+        let widget = flex!(vertical {
+            announcement: (flex::ItemSettings::Fixed, LiveRegion::new(self.announcement())),
+            controls: (flex::ItemSettings::Fixed, Padding::all_around(Aria::new("toolbar", "controls".to_string(), controls), 5.0)),
+            status: (flex::ItemSettings::Fixed, theme.label(self.status.clone().unwrap_or_default())),
+            breakpoints: (flex::ItemSettings::Fixed, self.view_breakpoints(theme)),
+            watches: (flex::ItemSettings::Fixed, self.view_watches(theme)),
+            current: (flex::ItemSettings::Flex(1.0), widget),
+        });
+
+        let widget =
+            if self.playing { Either::new_left(Timer::new((1000 / self.play_speed) as u32, |interpreter: &mut Interpreter| interpreter.play_step(), widget)) } else { Either::new_right(widget) };
+        // the class is always set because removing a property from the dom does not remove the class
+        CssClass::new(if self.reduce_motion { "reduce-motion" } else { "" }, widget)
+    }
+
+    // what screen readers read out after every step, which is the values of the variables that the step before it changed and then what the step is about to do
+    // the changes come first because they already happened, where the step has not run yet
+    fn announcement(&self) -> String {
+        let announcement = match &self.shown {
+            Shown::NotStarted => "not started".to_string(),
+            Shown::AboutToExecute { step, previous } => {
+                let line = self.history.info(self.position - 1).map(|info| info.line).unwrap_or_default();
+                let changes = previous.as_ref().map(|previous| changed_vars(&step.state.env, &previous.state.env)).unwrap_or_default();
+                let changes: String = changes.into_iter().map(|(name, value)| format!("{name} is now {value}. ")).collect();
+                format!("step {}, line {line}: {changes}about to {}", self.position, step.msg)
+            }
+            Shown::Finished => match self.history.finished() {
+                Some((_, Ok(()))) => "finished".to_string(),
+                Some((_, Err(err))) => format!("stopped with an error: {}", err.kind),
+                None => unreachable!("finished is only shown once the program has finished"),
+            },
+        };
+        match &self.status {
+            Some(status) => format!("{announcement}. {status}"),
+            None => announcement,
+        }
+    }
+
+    fn view_step_modes(&self, theme: &Theme) -> impl Widget<Interpreter> {
+        flex::homogeneous::Flex::new(
+            flex::Direction::Horizontal,
+            StepMode::ALL
+                .into_iter()
+                .map(|mode| {
+                    let text = if mode == self.step_mode { format!("[step by {}]", mode.name()) } else { format!("step by {}", mode.name()) };
+                    (flex::ItemSettings::Fixed, Clickable::new(MouseButton::Main, move |interpreter: &mut Interpreter| interpreter.step_mode = mode, Padding::all_around(theme.label(text), 5.0)))
+                })
+                .collect(),
+        )
+    }
+
+    fn view_breakpoints(&self, theme: &Theme) -> impl Widget<Interpreter> {
+        flex::homogeneous::Flex::new_vertical(
+            self.breakpoints
+                .iter()
+                .map(|(line, breakpoint)| {
+                    (
+                        flex::ItemSettings::Fixed,
+                        flex!(horizontal {
+                            line: (flex::ItemSettings::Fixed, Padding::all_around(theme.label(format!("breakpoint on line {line}")), 5.0)),
+                            condition: (
+                                flex::ItemSettings::Fixed,
+                                TextInput::new(breakpoint.condition_source.clone(), "condition (optional)".to_string(), move |interpreter: &mut Interpreter, condition| {
+                                    interpreter.breakpoints.set_condition(line, condition);
+                                })
+                            ),
+                            error: (
+                                flex::ItemSettings::Flex(1.0),
+                                Padding::all_around(theme.label(breakpoint.condition_error().map(|error| format!("invalid condition: {error}")).unwrap_or_default()), 5.0)
+                            ),
+                            remove: (
+                                flex::ItemSettings::Fixed,
+                                Clickable::new(
+                                    MouseButton::Main,
+                                    move |interpreter: &mut Interpreter| interpreter.breakpoints.toggle(line),
+                                    Padding::all_around(theme.label("remove".to_string()), 5.0)
+                                )
+                            ),
+                        }),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn view_watches(&self, theme: &Theme) -> impl Widget<Interpreter> {
+        let state: Option<&InterpreterState> = match &self.shown {
+            Shown::NotStarted => None,
+            Shown::AboutToExecute { step, .. } => Some(&step.state),
+            Shown::Finished => self.history.finished().map(|(final_state, _)| final_state),
+        };
+        let watches = self
+            .watches
+            .iter()
+            .map(|(index, watch)| {
+                let value = match state.map(|state| watch.evaluate(state)) {
+                    None => String::new(),
+                    Some(Ok(value)) => format!("= {value}"),
+                    Some(Err(error)) => format!("error: {error}"),
+                };
+                (
+                    flex::ItemSettings::Fixed,
+                    flex!(horizontal {
+                        expr: (
+                            flex::ItemSettings::Fixed,
+                            TextInput::new(watch.source.clone(), "expression".to_string(), move |interpreter: &mut Interpreter, source| interpreter.watches.set(index, source))
+                        ),
+                        value: (flex::ItemSettings::Flex(1.0), Padding::all_around(theme.code_label(value), 5.0)),
+                        remove: (
+                            flex::ItemSettings::Fixed,
+                            Clickable::new(MouseButton::Main, move |interpreter: &mut Interpreter| interpreter.watches.remove(index), Padding::all_around(theme.label("remove".to_string()), 5.0))
+                        ),
+                    }),
+                )
+            })
+            .collect();
+        flex!(vertical {
+            watches: (flex::ItemSettings::Fixed, flex::homogeneous::Flex::new_vertical(watches)),
+            add: (flex::ItemSettings::Fixed, TextInput::new(String::new(), "watch an expression".to_string(), |interpreter: &mut Interpreter, source| interpreter.watches.add(source))),
+        })
+    }
+
+    // the position that is after the last step that has been run
+    fn last_position(&self) -> usize {
+        self.history.len() + usize::from(self.history.finished().is_some())
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn go_to(&mut self, position: usize) {
+        // going past the steps that have already been run runs more of the program
+        while position > self.history.len() && self.history.run_step() {}
+        self.position = std::cmp::min(position, self.last_position());
+        self.shown = match self.position {
+            0 => Shown::NotStarted,
+            position => match self.history.get(position - 1) {
+                Some(step) => Shown::AboutToExecute { step, previous: position.checked_sub(2).and_then(|index| self.history.get(index)) },
+                None => Shown::Finished,
+            },
+        };
+    }
+
+    // moves forward until should_stop returns true for a position, the program finishes, or MAX_RUN_STEPS steps have been run
+    fn run_until(&mut self, mut should_stop: impl FnMut(&mut Self, usize) -> bool) {
+        self.status = None;
+        let mut position = self.position;
+        for steps_run in 0.. {
+            if steps_run == MAX_RUN_STEPS {
+                self.status = Some(format!("stopped after running {MAX_RUN_STEPS} steps"));
+                break;
+            }
+
+            position += 1;
+            while position > self.history.len() && self.history.run_step() {}
+            if position > self.history.len() {
+                // the program finished
+                break;
+            }
+            if should_stop(self, position) {
+                break;
+            }
+        }
+        self.go_to(position);
+    }
+
+    pub(crate) fn step_forward(&mut self) {
+        let from = self.position.checked_sub(1).and_then(|index| self.history.info(index));
+        let mode = self.step_mode;
+        self.run_until(|interpreter, position| interpreter.history.info(position - 1).is_some_and(|to| mode.stops_at(from, to)));
+    }
+
+    // goes back to the closest earlier step that stepping forward could have stopped at
+    // there is no way to know which block a step was entered from, so going back out of a block goes back one step at a time
+    pub(crate) fn step_back(&mut self) {
+        let mode = match self.step_mode {
+            StepMode::OutOfBlock => StepMode::Expression,
+            mode => mode,
+        };
+        let mut position = self.position.saturating_sub(1);
+        while position > 0 {
+            let previous = position.checked_sub(2).and_then(|index| self.history.info(index));
+            if self.history.info(position - 1).is_some_and(|info| mode.stops_at(previous, info)) {
+                break;
+            }
+            position -= 1;
+        }
+        self.status = None;
+        self.go_to(position);
+    }
+
+    pub(crate) fn continue_to_breakpoint(&mut self) {
+        self.run_until(Interpreter::breakpoint_stops_at);
+    }
+
+    // whether the step at the position is at a breakpoint that should stop, which also stops if the condition of the breakpoint could not be checked
+    fn breakpoint_stops_at(&mut self, position: usize) -> bool {
+        let Some(line) = self.history.info(position - 1).map(|info| info.line) else { return true };
+        // only the first step on a line can stop, otherwise continuing from a breakpoint would stop again at the next step on the same line
+        let entered_line = position == 1 || self.history.info(position - 2).map(|info| info.line) != Some(line);
+        if !(entered_line && self.breakpoints.contains(line)) {
+            return false;
+        }
+        let history = &mut self.history;
+        match self.breakpoints.should_stop(line, || history.get(position - 1).expect("step should be in history").state.clone()) {
+            Ok(stop) => stop,
+            Err(message) => {
+                self.status = Some(format!("stopped because the condition of the breakpoint on line {line} could not be checked: {message}"));
+                true
+            }
+        }
+    }
+
+    pub(crate) fn toggle_playing(&mut self) {
+        self.playing = !self.playing;
+        // playing from the end plays the program again
+        if self.playing && matches!(self.shown, Shown::Finished) {
+            self.go_to(0);
+        }
+    }
+
+    // goes back to before the first step, which is the same as running the program again because it always does the same thing
+    pub(crate) fn restart(&mut self) {
+        self.playing = false;
+        self.status = None;
+        self.go_to(0);
+    }
+
+    // one step of playing, which stops playing at breakpoints, errors and the end of the program
+    fn play_step(&mut self) {
+        let from = self.position.checked_sub(1).and_then(|index| self.history.info(index));
+        let mode = self.step_mode;
+        let mut at_breakpoint = false;
+        self.run_until(|interpreter, position| {
+            at_breakpoint = interpreter.breakpoint_stops_at(position);
+            at_breakpoint || interpreter.history.info(position - 1).is_some_and(|to| mode.stops_at(from, to))
+        });
+        if at_breakpoint || self.status.is_some() || matches!(self.shown, Shown::Finished) {
+            self.playing = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        app::{theme::Theme, vdom, widgets::Widget},
+        interpreter::interpreter::{new_interpreter, test_program, Interpreter},
+    };
+
+    fn interpreter(source: &str) -> Interpreter {
+        let (file, stmts) = test_program(source);
+        new_interpreter(file, stmts, None, 10)
+    }
+
+    fn with_prop<'vdom>(vdom: &'vdom vdom::Element<Interpreter>, name: &str, value: &str) -> Vec<&'vdom vdom::Element<Interpreter>> {
+        vdom.descendants().into_iter().filter(|element| element.prop(name) == Some(&value.into())).collect()
+    }
+
+    #[test]
+    fn announcements() {
+        let mut interpreter = interpreter("var x = 1;\nx = 2;\nprint x;\n");
+        let announcement = |interpreter: &Interpreter| {
+            let vdom = interpreter.view(&Theme::LIGHT).to_vdom();
+            let live_regions = with_prop(&vdom, "aria-live", "polite");
+            assert_eq!(live_regions.len(), 1);
+            assert_eq!(live_regions[0].prop("role"), Some(&"status".into()));
+            live_regions[0].text()
+        };
+
+        assert_eq!(announcement(&interpreter), "not started");
+        interpreter.step_forward();
+        assert_eq!(announcement(&interpreter), "step 1, line 1: about to make variable 'x' with initializer 1");
+        interpreter.step_forward();
+        assert_eq!(announcement(&interpreter), "step 2, line 2: x is now 1. about to assign variable 'x' with value 2");
+        interpreter.step_forward();
+        // the step before the print reads 'x', so nothing changed since the step before it
+        assert_eq!(announcement(&interpreter), "step 4, line 3: about to print value 2");
+    }
+
+    #[test]
+    fn breakpoint_condition_errors_stop() {
+        let mut interpreter = interpreter("var i = 0;\nwhile i < 3 {\n    i = i + 1;\n}\n");
+        interpreter.breakpoints.toggle(3);
+        interpreter.breakpoints.set_condition(3, "i / 0 == 1".to_string());
+        interpreter.continue_to_breakpoint();
+        assert_eq!(interpreter.status.as_deref(), Some("stopped because the condition of the breakpoint on line 3 could not be checked: division by zero"));
+    }
+
+    #[test]
+    fn panes_are_labeled() {
+        let mut interpreter = interpreter("var x = 1;\nprint x;\n");
+        interpreter.step_forward();
+        let vdom = interpreter.view(&Theme::LIGHT).to_vdom();
+        let labels: Vec<_> = with_prop(&vdom, "role", "region").into_iter().filter_map(|region| region.prop("aria-label")?.as_string()).collect();
+        assert_eq!(labels, ["code", "program output", "variables", "expression tree"]);
+    }
+
+    #[test]
+    fn output_is_shown_after_an_error() {
+        let mut interpreter = interpreter("print 1;\nprint 2 / 0;\n");
+        interpreter.continue_to_breakpoint();
+        let vdom = interpreter.view(&Theme::LIGHT).to_vdom();
+        let output = with_prop(&vdom, "aria-label", "program output");
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].text(), "1");
+    }
+
+    #[test]
+    fn controls_are_keyboard_operable() {
+        let mut interpreter = interpreter("var x = 1;\nprint x;\nprint x + 1;\n");
+        interpreter.step_forward();
+        interpreter.step_forward();
+        interpreter.step_forward();
+        let vdom = interpreter.view(&Theme::LIGHT).to_vdom();
+        let clickable: Vec<_> = vdom.descendants().into_iter().filter(|element| element.event_listeners.iter().any(|(event, _)| *event == "click")).collect();
+        assert!(!clickable.is_empty());
+        for element in clickable {
+            assert_eq!(element.prop("tabIndex"), Some(&0.into()), "clickable element with text {:?} should be focusable", element.text());
+            assert!(element.event_listeners.iter().any(|(event, _)| *event == "keydown"), "clickable element with text {:?} should respond to keys", element.text());
+        }
+    }
+}
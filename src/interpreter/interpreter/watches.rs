@@ -0,0 +1,126 @@
+use crate::{
+    error::Error,
+    interpreter::{
+        interpreter::interpreter::{evaluate_detached, value::ReprValue, InterpreterState},
+        lang::Expr,
+        parser,
+    },
+    source::File,
+};
+
+// expressions that are shown with their value at every step
+pub(super) struct Watches {
+    watches: Vec<Watch>,
+}
+pub(super) struct Watch {
+    // kept so that the expression can be shown the way that it was typed
+    pub(super) source: String,
+    // the message of the first error if the expression could not be parsed
+    expr: Result<Expr, String>,
+}
+
+impl Watch {
+    fn new(source: String) -> Watch {
+        // the errors only ever point into the expression, so only the message is kept
+        let expr = parser::parse_expr(&File::new("watch expression".to_string(), source.clone()))
+            .map_err(|errors| errors.first().map_or_else(|| "invalid expression".to_string(), |error| error.main_message.clone()));
+        Watch { source, expr }
+    }
+
+    // the value of the expression with the variables in the state, or an error message
+    // evaluating the expression never changes the program, so an error here does not stop it
+    pub(super) fn evaluate(&self, state: &InterpreterState) -> Result<String, String> {
+        let expr = self.expr.as_ref().map_err(|message| format!("invalid expression: {message}"))?;
+        evaluate_detached(state, expr.clone()).map(|value| ReprValue(&value).to_string()).map_err(|err| Error::from(&err).main_message)
+    }
+}
+
+impl Watches {
+    pub(super) fn new() -> Watches {
+        Watches { watches: Vec::new() }
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = (usize, &Watch)> {
+        self.watches.iter().enumerate()
+    }
+
+    // empty expressions are not added
+    pub(super) fn add(&mut self, source: String) {
+        if !source.trim().is_empty() {
+            self.watches.push(Watch::new(source));
+        }
+    }
+
+    // changing an expression to be empty removes it
+    pub(super) fn set(&mut self, index: usize, source: String) {
+        if source.trim().is_empty() {
+            self.remove(index);
+        } else if let Some(watch) = self.watches.get_mut(index) {
+            *watch = Watch::new(source);
+        }
+    }
+
+    pub(super) fn remove(&mut self, index: usize) {
+        if index < self.watches.len() {
+            self.watches.remove(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interpreter::interpreter::{interpreter::InterpreterState, state_after, watches::Watches};
+
+    fn values(watches: &Watches, state: &InterpreterState) -> Vec<Result<String, String>> {
+        watches.iter().map(|(_, watch)| watch.evaluate(state)).collect()
+    }
+
+    #[test]
+    fn evaluate() {
+        let mut watches = Watches::new();
+        watches.add("iter % 15".to_string());
+        watches.add("fizz && buzz".to_string());
+        watches.add("x".to_string());
+        watches.add("iter +".to_string());
+        watches.add("  ".to_string());
+
+        let state = state_after("var iter = 20;\nvar fizz = false;\nvar buzz = true;\nvar x;");
+        assert_eq!(
+            values(&watches, &state),
+            [Ok("5".to_string()), Ok("false".to_string()), Err("variable 'x' is uninitialized".to_string()), Err("invalid expression: expected expression".to_string())]
+        );
+        // the state is not changed by evaluating the expressions
+        assert_eq!(values(&watches, &state)[0], Ok("5".to_string()));
+    }
+
+    #[test]
+    fn errors_do_not_panic() {
+        let mut watches = Watches::new();
+        watches.add("x / 0".to_string());
+        watches.add("x % (x - 3)".to_string());
+        watches.add("max(x, 1)".to_string());
+        watches.add("x / 2.0".to_string());
+
+        let state = state_after("var x = 3;");
+        assert_eq!(
+            values(&watches, &state),
+            [
+                Err("division by zero".to_string()),
+                Err("division by zero".to_string()),
+                Err("functions cannot be called".to_string()),
+                Err("invalid types 'int' and 'float' to operator '/'".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn edit() {
+        let mut watches = Watches::new();
+        watches.add("a".to_string());
+        watches.add("b".to_string());
+        watches.set(0, "a + 1".to_string());
+        watches.set(1, String::new());
+        watches.remove(5);
+        assert_eq!(watches.iter().map(|(_, watch)| watch.source.as_str()).collect::<Vec<_>>(), ["a + 1"]);
+    }
+}
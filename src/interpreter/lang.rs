@@ -4,7 +4,7 @@ use std::fmt::Display;
 
 use crate::source::{Located, Span};
 
-#[derive(Eq, PartialEq, Hash, Clone)]
+#[derive(Eq, PartialEq, Hash, Clone, Debug)]
 pub(crate) struct VarName(pub(crate) String);
 impl Display for VarName {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -13,12 +13,12 @@ impl Display for VarName {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub(crate) enum UnaryOp {
     NumericNegate,
     LogicalNegate,
 }
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub(crate) enum BinaryOp {
     Equal,
     NotEqual,
@@ -32,7 +32,7 @@ pub(crate) enum BinaryOp {
     Divide,
     Modulo,
 }
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub(crate) enum ShortCircuitOp {
     Or,
     And,
@@ -71,13 +71,13 @@ impl Display for ShortCircuitOp {
     }
 }
 
-#[derive(Clone)]
-pub(crate) struct Expr<'file> {
-    pub(crate) kind: ExprKind<'file>,
-    pub(crate) span: Span<'file>,
+#[derive(Clone, Debug)]
+pub(crate) struct Expr {
+    pub(crate) kind: ExprKind,
+    pub(crate) span: Span,
 }
-#[derive(Clone)]
-pub(crate) enum ExprKind<'file> {
+#[derive(Clone, Debug)]
+pub(crate) enum ExprKind {
     Var(VarName),
 
     Int(BigInt),
@@ -85,28 +85,28 @@ pub(crate) enum ExprKind<'file> {
     String(String),
     Bool(bool),
 
-    Parenthesized(Box<Expr<'file>>),
+    Parenthesized(Box<Expr>),
 
-    Call(Box<Expr<'file>>, Vec<Expr<'file>>),
+    Call(Box<Expr>, Vec<Expr>),
 
-    ShortCircuitOp(Box<Expr<'file>>, Located<'file, ShortCircuitOp>, Box<Expr<'file>>),
-    BinaryOp(Box<Expr<'file>>, Located<'file, BinaryOp>, Box<Expr<'file>>),
-    UnaryOp(Located<'file, UnaryOp>, Box<Expr<'file>>),
+    ShortCircuitOp(Box<Expr>, Located<ShortCircuitOp>, Box<Expr>),
+    BinaryOp(Box<Expr>, Located<BinaryOp>, Box<Expr>),
+    UnaryOp(Located<UnaryOp>, Box<Expr>),
 }
 
-#[derive(Clone)]
-pub(crate) struct Stmt<'file> {
-    pub(crate) kind: StmtKind<'file>,
-    pub(crate) span: Span<'file>,
+#[derive(Clone, Debug)]
+pub(crate) struct Stmt {
+    pub(crate) kind: StmtKind,
+    pub(crate) span: Span,
 }
-#[derive(Clone)]
-pub(crate) enum StmtKind<'file> {
-    Block(Vec<Stmt<'file>>),
-    Expr(Expr<'file>),
-    Print(Expr<'file>),
-    Return(Expr<'file>),
-    MakeVar(VarName, Option<Expr<'file>>),
-    AssignVar(VarName, Expr<'file>),
-    If(Span<'file>, Expr<'file>, Box<Stmt<'file>>, Option<Box<Stmt<'file>>>),
-    While(Span<'file>, Expr<'file>, Box<Stmt<'file>>),
+#[derive(Clone, Debug)]
+pub(crate) enum StmtKind {
+    Block(Vec<Stmt>),
+    Expr(Expr),
+    Print(Expr),
+    Return(Expr),
+    MakeVar(VarName, Option<Expr>),
+    AssignVar(Located<VarName>, Expr),
+    If(Span, Expr, Box<Stmt>, Option<Box<Stmt>>),
+    While(Span, Expr, Box<Stmt>),
 }
@@ -1,11 +1,16 @@
+use std::rc::Rc;
+
 use crate::{
-    error::{Error, ErrorReportedPromise, Report},
+    error::{catalog, Error, ErrorReportedPromise},
     interpreter::lang::{Expr, Stmt},
     interpreter::parser::token::Token,
-    source::File,
+    source::{File, Located},
 };
 
-mod token;
+#[cfg(feature = "web")]
+use crate::source::Span;
+
+pub(crate) mod token;
 
 mod lexer;
 #[allow(clippy::module_inception)]
@@ -14,23 +19,157 @@ mod parser;
 mod expr;
 mod stmt;
 
-pub(crate) fn parse_expr(file: &File) -> Result<Expr, ErrorReportedPromise> {
+pub(crate) fn parse_expr(file: &Rc<File>) -> Result<Expr, Vec<Error>> {
     let mut parser = parser::Parser::new(lexer::Lexer::new(file));
-    let expr = expr::expression(&mut parser);
-    parser.consume(|tok| match tok.1 {
-        Token::Eof => Ok(()),
-        _ => Err(Error::new(Some(tok.0), "extraneous input".to_string()).report()),
-    })?;
-    expr
+    let expr = expr::expression(&mut parser).and_then(|expr| {
+        parser.consume(|tok| match tok.1 {
+            Token::Eof => Ok(()),
+            _ => Err(Error::new(Some(tok.0), "extraneous input".to_string()).with_code(&catalog::EXTRANEOUS_INPUT)),
+        })?;
+        Ok(expr)
+    });
+    finish(parser, expr)
 }
 
-pub(crate) fn parse_statements(file: &File) -> Result<Vec<Stmt>, ErrorReportedPromise> {
+pub(crate) fn parse_statements(file: &Rc<File>) -> Result<Vec<Stmt>, Vec<Error>> {
     let mut parser = parser::Parser::new(lexer::Lexer::new(file));
-    let mut statements = Vec::new();
-    while !parser.peek_matches(|tok| matches!(tok, Token::Eof)) {
-        statements.push(stmt::statement(&mut parser)?);
-        // TODO: panic mode error recovery?
+    let statements = (|| {
+        let mut statements = Vec::new();
+        while !parser.peek_matches(|tok| matches!(tok, Token::Eof)) {
+            statements.push(stmt::statement(&mut parser)?);
+            // TODO: panic mode error recovery?
+        }
+        Ok(statements)
+    })();
+    finish(parser, statements)
+}
+
+// lexes the whole file up to and including the eof token
+pub(crate) fn lex(file: &Rc<File>) -> (Vec<Located<Token>>, Vec<Error>) {
+    let mut lexer = lexer::Lexer::new(file);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next();
+        let is_eof = matches!(token.1, Token::Eof);
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+    (tokens, lexer.errors.finish())
+}
+
+// how a part of the source code is shown in the code view
+#[cfg(feature = "web")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum SyntaxClass {
+    Keyword,
+    Literal,
+    Identifier,
+    Operator,
+    Comment,
+}
+
+// the parts of the file that are styled, in order
+// punctuation, whitespace, and anything that could not be lexed are left out
+#[cfg(feature = "web")]
+pub(crate) fn syntax_classes(file: &Rc<File>) -> Vec<(Span, SyntaxClass)> {
+    let mut lexer = lexer::Lexer::new(file);
+    let mut classes = Vec::new();
+    loop {
+        let Located(span, token) = lexer.next();
+        let class = match token {
+            Token::Eof => break,
+            Token::OParen | Token::CParen | Token::OBrack | Token::CBrack | Token::OBrace | Token::CBrace | Token::Semicolon | Token::Period | Token::Comma => None,
+            Token::Equal
+            | Token::Bang
+            | Token::Plus
+            | Token::Minus
+            | Token::Star
+            | Token::Slash
+            | Token::Percent
+            | Token::Pipe
+            | Token::Amper
+            | Token::DoublePipe
+            | Token::DoubleAmper
+            | Token::SlashEqual
+            | Token::StarEqual
+            | Token::MinusEqual
+            | Token::PlusEqual
+            | Token::PercentEqual
+            | Token::BangEqual
+            | Token::DoubleEqual
+            | Token::Greater
+            | Token::GreaterEqual
+            | Token::Less
+            | Token::LessEqual => Some(SyntaxClass::Operator),
+            Token::Identifier(_) => Some(SyntaxClass::Identifier),
+            Token::IntLit(_) | Token::FloatLit(_) | Token::StrLit(_) | Token::BoolLit(_) => Some(SyntaxClass::Literal),
+            Token::If | Token::Else | Token::For | Token::While | Token::Break | Token::Continue | Token::Var | Token::Return | Token::Fn | Token::Assign | Token::To | Token::Print | Token::Make => {
+                Some(SyntaxClass::Keyword)
+            }
+        };
+        classes.extend(class.map(|class| (span, class)));
+    }
+    classes.extend(lexer.comments.drain(..).map(|comment| (comment, SyntaxClass::Comment)));
+    classes.sort_by_key(|(span, _)| span.start);
+    classes
+}
+
+// errors that the lexer recovers from still have to make the whole parse fail
+fn finish<'file, T>(parser: parser::Parser<'file>, result: Result<T, ErrorReportedPromise>) -> Result<T, Vec<Error>> {
+    let errors = parser.finish();
+    match result {
+        Ok(result) if errors.is_empty() => Ok(result),
+        _ => Err(errors),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{error::Footnote, interpreter::parser::parse_statements, source::File};
+
+    #[test]
+    fn recovered_lex_errors_fail_parse() {
+        let file = File::new("test.pel".to_string(), "var x = 1 $;\nprint \"abc;".to_string());
+        let errors = parse_statements(&file).expect_err("parse should fail");
+        assert_eq!(errors.iter().map(|error| error.main_message.as_str()).collect::<Vec<_>>(), ["bad character '$'", "unterminated string literal", "expected expression"]);
+    }
+
+    #[test]
+    fn misspelled_keyword_suggestion() {
+        let file = File::new("test.pel".to_string(), "var x = 0;\nwhlie x < 3 {\n    x = x + 1;\n}\n".to_string());
+        let errors = parse_statements(&file).expect_err("parse should fail");
+        match &errors[0].footnotes[..] {
+            [Footnote::Help { message, replacement: Some((span, replacement)) }] => {
+                assert_eq!(message, "did you mean 'while'?");
+                assert_eq!((span.start, span.end, replacement.as_str()), (11, 16, "while"));
+            }
+            _ => panic!("expected a suggestion"),
+        }
     }
 
-    Ok(statements)
+    #[test]
+    #[cfg(feature = "web")]
+    fn syntax() {
+        use crate::interpreter::parser::{syntax_classes, SyntaxClass};
+
+        let file = File::new("test.pel".to_string(), "var x = 1; // one\nprint x == true; // end".to_string());
+        let classes: Vec<_> = syntax_classes(&file).into_iter().map(|(span, class)| (&file.source[span.start..span.end], class)).collect();
+        assert_eq!(
+            classes,
+            [
+                ("var", SyntaxClass::Keyword),
+                ("x", SyntaxClass::Identifier),
+                ("=", SyntaxClass::Operator),
+                ("1", SyntaxClass::Literal),
+                ("// one", SyntaxClass::Comment),
+                ("print", SyntaxClass::Keyword),
+                ("x", SyntaxClass::Identifier),
+                ("==", SyntaxClass::Operator),
+                ("true", SyntaxClass::Literal),
+                ("// end", SyntaxClass::Comment),
+            ]
+        );
+    }
 }
@@ -1,22 +1,22 @@
 use crate::{
-    error::{Error, ErrorReportedPromise, Report},
+    error::{catalog, Error, ErrorReportedPromise},
     interpreter::lang::{BinaryOp, Expr, ExprKind, ShortCircuitOp, UnaryOp, VarName},
     interpreter::parser::{parser::Parser, token::Token},
     source::Located,
 };
 
-pub(super) fn expression<'file>(parser: &mut Parser<'file>) -> Result<Expr<'file>, ErrorReportedPromise> {
+pub(super) fn expression<'file>(parser: &mut Parser<'file>) -> Result<Expr, ErrorReportedPromise> {
     or(parser)
 }
 
 macro_rules! left_associative_binary_op {
     ($name:ident, $next_level:ident, $expr_variant:ident, $operator_predicate:expr $(,)?) => {
-        fn $name<'file>(parser: &mut Parser<'file>) -> Result<Expr<'file>, ErrorReportedPromise> {
+        fn $name<'file>(parser: &mut Parser<'file>) -> Result<Expr, ErrorReportedPromise> {
             let mut left = $next_level(parser)?;
 
             while let Some(op) = parser.maybe_consume($operator_predicate) {
                 let right = $next_level(parser)?;
-                let span = left.span + right.span;
+                let span = left.span.clone() + right.span.clone();
                 left = Expr { kind: ExprKind::$expr_variant(Box::new(left), op, Box::new(right)), span };
             }
 
@@ -57,25 +57,25 @@ left_associative_binary_op!(factor, unary, BinaryOp, |tok| match tok.1 {
     _ => None,
 });
 
-fn unary<'file>(parser: &mut Parser<'file>) -> Result<Expr<'file>, ErrorReportedPromise> {
+fn unary<'file>(parser: &mut Parser<'file>) -> Result<Expr, ErrorReportedPromise> {
     if let Some(operator) = parser.maybe_consume(|tok| match tok.1 {
         Token::Bang => Some(Located(tok.0, UnaryOp::LogicalNegate)),
         Token::Minus => Some(Located(tok.0, UnaryOp::NumericNegate)),
         _ => None,
     }) {
         let operand = unary(parser)?;
-        let total_span = operator.0 + operand.span;
+        let total_span = operator.0.clone() + operand.span.clone();
         Ok(Expr { kind: ExprKind::UnaryOp(operator, Box::new(operand)), span: total_span })
     } else {
         call(parser)
     }
 }
 
-fn call<'file>(parser: &mut Parser<'file>) -> Result<Expr<'file>, ErrorReportedPromise> {
+fn call<'file>(parser: &mut Parser<'file>) -> Result<Expr, ErrorReportedPromise> {
     let mut expr = primary(parser)?;
 
-    while let Some(()) = parser.maybe_consume(|tok| match tok.1 {
-        Token::OParen => Some(()),
+    while let Some(oparen_sp) = parser.maybe_consume(|tok| match tok.1 {
+        Token::OParen => Some(tok.0),
         _ => None,
     }) {
         let mut arguments = Vec::new();
@@ -91,10 +91,12 @@ fn call<'file>(parser: &mut Parser<'file>) -> Result<Expr<'file>, ErrorReportedP
 
         let cparen_sp = parser.consume(|tok| match tok.1 {
             Token::CParen => Ok(tok.0),
-            _ => Err({ Error::new(Some(tok.0), "expected ')' after arguments".to_string()) }.report()),
+            _ => {
+                Err(Error::new(Some(tok.0), "expected ')' after arguments".to_string()).with_code(&catalog::UNCLOSED_DELIMITER).with_label(oparen_sp.clone(), "argument list starts here".to_string()))
+            }
         })?;
 
-        let total_span = expr.span + cparen_sp;
+        let total_span = expr.span.clone() + cparen_sp;
 
         expr = Expr { kind: ExprKind::Call(Box::new(expr), arguments), span: total_span };
     }
@@ -102,7 +104,7 @@ fn call<'file>(parser: &mut Parser<'file>) -> Result<Expr<'file>, ErrorReportedP
     Ok(expr)
 }
 
-fn primary<'file>(parser: &mut Parser<'file>) -> Result<Expr<'file>, ErrorReportedPromise> {
+fn primary<'file>(parser: &mut Parser<'file>) -> Result<Expr, ErrorReportedPromise> {
     let next = parser.next();
     match next.1 {
         Token::Identifier(n) => Ok(Expr { kind: ExprKind::Var(VarName(n)), span: next.0 }),
@@ -116,12 +118,14 @@ fn primary<'file>(parser: &mut Parser<'file>) -> Result<Expr<'file>, ErrorReport
 
             let cparen_sp = parser.consume(|tok| match tok.1 {
                 Token::CParen => Ok(tok.0),
-                _ => Err({ Error::new(Some(tok.0), "expected ')' to close parenthesized expression".to_string()) }.report()),
+                _ => Err(Error::new(Some(tok.0), "expected ')' to close parenthesized expression".to_string())
+                    .with_code(&catalog::UNCLOSED_DELIMITER)
+                    .with_label(next.0.clone(), "parenthesized expression starts here".to_string())),
             })?;
 
             Ok(Expr { kind: ExprKind::Parenthesized(Box::new(inner)), span: next.0 + cparen_sp })
         }
 
-        _ => Err(Error::new(Some(next.0), "expected expression".to_string()).report()),
+        _ => Err(parser.report(Error::new(Some(next.0), "expected expression".to_string()).with_code(&catalog::EXPECTED_EXPRESSION))),
     }
 }
@@ -1,38 +1,67 @@
-use std::{iter::Peekable, str::CharIndices};
+use std::{iter::Peekable, rc::Rc, str::CharIndices};
 
 use crate::{
-    error::{Error, ErrorReportedPromise, Report},
+    error::{catalog, Error, ErrorCollector, ErrorReportedPromise},
     interpreter::parser::token::Token,
     source::{File, Located, Span},
 };
 
-enum LexError<'file> {
-    UnterminatedString(Span<'file>),
-    BadCharacter(Span<'file>, char),
+enum LexError {
+    UnterminatedString(Span),
+    BadCharacter(Span, char),
 }
 
-impl<'file> From<LexError<'file>> for Error<'file> {
-    fn from(val: LexError<'file>) -> Self {
+impl From<LexError> for Error {
+    fn from(val: LexError) -> Self {
         match val {
-            LexError::UnterminatedString(sp) => Error::new(Some(sp), "unterminated string literal".into()),
+            LexError::UnterminatedString(sp) => {
+                Error::new(Some(sp), "unterminated string literal".into()).with_code(&catalog::UNTERMINATED_STRING).with_help("add a '\"' where the string should end".into())
+            }
 
-            LexError::BadCharacter(sp, ch) if ch.is_ascii() => Error::new(Some(sp), format!("bad character '{ch}'")),
-            LexError::BadCharacter(sp, ch) => Error::new(Some(sp), format!("bad non-ascii character '{ch}'")),
+            LexError::BadCharacter(sp, ch) if ch.is_ascii() => Error::new(Some(sp), format!("bad character '{ch}'")).with_code(&catalog::BAD_CHARACTER),
+            LexError::BadCharacter(sp, ch) => {
+                Error::new(Some(sp), format!("bad non-ascii character '{ch}'")).with_code(&catalog::BAD_CHARACTER).with_note("non-ascii characters are only allowed inside of string literals".into())
+            }
         }
     }
 }
 
-pub(super) struct Lexer<'file>(&'file File, Peekable<CharIndices<'file>>);
+// also used for suggesting keywords when one is misspelled
+pub(super) const KEYWORDS: &[(&str, Token)] = &[
+    ("if", Token::If),
+    ("else", Token::Else),
+    ("for", Token::For),
+    ("while", Token::While),
+    ("break", Token::Break),
+    ("continue", Token::Continue),
+    ("var", Token::Var),
+    ("return", Token::Return),
+    ("fn", Token::Fn),
+    ("assign", Token::Assign),
+    ("make", Token::Make),
+    ("print", Token::Print),
+    ("to", Token::To),
+    ("true", Token::BoolLit(true)),
+    ("false", Token::BoolLit(false)),
+];
+
+pub(super) struct Lexer<'file> {
+    file: &'file Rc<File>,
+    chars: Peekable<CharIndices<'file>>,
+    pub(super) errors: ErrorCollector,
+    // comments are not tokens, so they are kept here for syntax highlighting
+    pub(super) comments: Vec<Span>,
+}
 impl<'file> Lexer<'file> {
-    pub(super) fn new(file: &'file File) -> Lexer<'file> {
-        Lexer(file, file.source.char_indices().peekable())
+    pub(super) fn new(file: &'file Rc<File>) -> Lexer<'file> {
+        Lexer { file, chars: file.source.char_indices().peekable(), errors: ErrorCollector::new(), comments: Vec::new() }
     }
 
     fn pos(&mut self) -> Option<usize> {
-        self.1.peek().map(|(i, _)| *i)
+        self.chars.peek().map(|(i, _)| *i)
     }
     fn peek(&mut self) -> Option<char> {
-        self.1.peek().map(|(_, a)| *a)
+        self.chars.peek().map(|(_, a)| *a)
     }
 
     fn check_peek_matches_and_consume(&mut self, ch: char) -> bool {
@@ -45,33 +74,33 @@ impl<'file> Lexer<'file> {
     }
 
     fn slice(&self, start: usize, end: usize) -> &str {
-        &self.0.source[start..end]
+        &self.file.source[start..end]
     }
 
     fn slice_from(&mut self, start: usize) -> &str {
         if let Some(end) = self.pos() {
-            &self.0.source[start..end]
+            &self.file.source[start..end]
         } else {
-            &self.0.source[start..]
+            &self.file.source[start..]
         }
     }
 
     /* (unused function)
-    fn span(&self, (start): Ind, (end): Ind) -> Span<'file> {
+    fn span(&self, (start): Ind, (end): Ind) -> Span {
         Span(self.0, start, end)
     }
     */
 
-    fn span_from(&mut self, start: usize) -> Span<'file> {
+    fn span_from(&mut self, start: usize) -> Span {
         if let Some(end) = self.pos() {
-            Span::new_from_start_and_end(self.0, start, end)
+            Span::new_from_start_and_end(self.file, start, end)
         } else {
-            Span::new_from_start_and_end(self.0, start, self.0.source.len())
+            Span::new_from_start_and_end(self.file, start, self.file.source.len())
         }
     }
 
-    fn string(&mut self, start: usize) -> Result<Located<'file, Token>, ErrorReportedPromise> {
-        while let Some((end, c)) = self.1.next() {
+    fn string(&mut self, start: usize) -> Result<Located<Token>, ErrorReportedPromise> {
+        while let Some((end, c)) = self.chars.next() {
             if c == '\"' {
                 let sp = self.span_from(start);
                 let tok = Token::StrLit(self.slice(start + 1, end).into());
@@ -79,18 +108,19 @@ impl<'file> Lexer<'file> {
             }
         }
 
-        Err(LexError::UnterminatedString(self.span_from(start)).report())
+        let sp = self.span_from(start);
+        Err(self.errors.report(LexError::UnterminatedString(sp)))
     }
 
-    fn number(&mut self, start: usize) -> Located<'file, Token> {
+    fn number(&mut self, start: usize) -> Located<Token> {
         while self.peek().map_or(false, |c| c.is_ascii_digit()) {
-            self.1.next();
+            self.chars.next();
         }
 
         if self.peek() == Some('.') {
-            self.1.next();
+            self.chars.next();
             while self.peek().map_or(false, |c| c.is_ascii_digit()) {
-                self.1.next();
+                self.chars.next();
             }
 
             let tok = Token::FloatLit(self.slice_from(start).parse().unwrap());
@@ -101,46 +131,32 @@ impl<'file> Lexer<'file> {
         }
     }
 
-    fn alpha_iden(&mut self, start: usize) -> Located<'file, Token> {
+    fn alpha_iden(&mut self, start: usize) -> Located<Token> {
         while self.peek().map_or(false, |c| c.is_ascii_alphanumeric() || c == '_') {
-            self.1.next();
+            self.chars.next();
         }
-        Located(
-            self.span_from(start),
-            match self.slice_from(start) {
-                "if" => Token::If,
-                "else" => Token::Else,
-                "for" => Token::For,
-                "while" => Token::While,
-                "break" => Token::Break,
-                "continue" => Token::Continue,
-                "var" => Token::Var,
-                "return" => Token::Return,
-                "fn" => Token::Fn,
-                "assign" => Token::Assign,
-                "make" => Token::Make,
-                "print" => Token::Print,
-                "to" => Token::To,
-                "true" => Token::BoolLit(true),
-                "false" => Token::BoolLit(false),
-                iden => Token::Identifier(iden.into()),
-            },
-        )
+        let token = match KEYWORDS.iter().find(|(keyword, _)| *keyword == self.slice_from(start)) {
+            Some((_, token)) => token.clone(),
+            None => Token::Identifier(self.slice_from(start).into()),
+        };
+        Located(self.span_from(start), token)
     }
 
-    pub(super) fn next(&mut self) -> Located<'file, Token> {
-        let Some((start_ind, c)) = self.1.next() else {
-            return Located(self.0.eof_span(), Token::Eof);
+    pub(super) fn next(&mut self) -> Located<Token> {
+        let Some((start_ind, c)) = self.chars.next() else {
+            return Located(self.file.eof_span(), Token::Eof);
         };
 
         match c {
             '/' if self.peek() == Some('/') => {
                 loop {
-                    if let Some(&(_, '\n')) = self.1.peek() {
+                    if let Some(&(_, '\n')) | None = self.chars.peek() {
                         break;
                     }
-                    self.1.next();
+                    self.chars.next();
                 }
+                let comment = self.span_from(start_ind);
+                self.comments.push(comment);
 
                 self.next()
             }
@@ -246,7 +262,8 @@ impl<'file> Lexer<'file> {
             c if c.is_ascii_alphabetic() || c == '_' => self.alpha_iden(start_ind),
 
             _ => {
-                LexError::BadCharacter(self.span_from(start_ind), c).report();
+                let sp = self.span_from(start_ind);
+                self.errors.report(LexError::BadCharacter(sp, c));
                 self.next()
             }
         }
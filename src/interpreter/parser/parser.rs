@@ -1,12 +1,12 @@
 use crate::{
-    error::ErrorReportedPromise,
+    error::{Error, ErrorReportedPromise},
     interpreter::parser::{lexer::Lexer, token::Token},
     source::Located,
 };
 
 pub(super) struct Parser<'file> {
     lexer: Lexer<'file>,
-    peek: Option<Located<'file, Token>>,
+    peek: Option<Located<Token>>,
 }
 
 impl<'file> Parser<'file> {
@@ -14,20 +14,28 @@ impl<'file> Parser<'file> {
         Parser { lexer, peek: None }
     }
 
+    pub(super) fn report(&mut self, error: Error) -> ErrorReportedPromise {
+        self.lexer.errors.report(error)
+    }
+
+    pub(super) fn finish(self) -> Vec<Error> {
+        self.lexer.errors.finish()
+    }
+
     fn fill_peek(&mut self) {
         if self.peek.is_none() {
             self.peek = Some(self.lexer.next());
         }
     }
 
-    pub(super) fn next(&mut self) -> Located<'file, Token> {
+    pub(super) fn next(&mut self) -> Located<Token> {
         match self.peek.take() {
             Some(tok) => tok,
             None => self.lexer.next(),
         }
     }
 
-    pub(super) fn peek(&mut self) -> &Located<'file, Token> {
+    pub(super) fn peek(&mut self) -> &Located<Token> {
         self.fill_peek();
         self.peek.as_ref().expect("peek should not be None because it was just filled")
     }
@@ -36,12 +44,12 @@ impl<'file> Parser<'file> {
         pred(&self.peek().1)
     }
 
-    pub(super) fn consume<R>(&mut self, f: impl Fn(Located<'file, Token>) -> Result<R, ErrorReportedPromise>) -> Result<R, ErrorReportedPromise> {
+    pub(super) fn consume<R>(&mut self, f: impl Fn(Located<Token>) -> Result<R, Error>) -> Result<R, ErrorReportedPromise> {
         self.fill_peek();
-        f(self.peek.take().expect("peek should not be None because it was just filled"))
+        f(self.peek.take().expect("peek should not be None because it was just filled")).map_err(|err| self.report(err))
     }
 
-    pub(super) fn maybe_consume<R>(&mut self, f: impl Fn(Located<'file, Token>) -> Option<R>) -> Option<R> {
+    pub(super) fn maybe_consume<R>(&mut self, f: impl Fn(Located<Token>) -> Option<R>) -> Option<R> {
         self.fill_peek();
         let result = f(self.peek.as_ref().expect("peek should not be None because it was just filled").clone());
         if let Some(result) = result {
@@ -1,11 +1,11 @@
 use crate::{
-    error::{Error, ErrorReportedPromise, Report},
+    error::{catalog, suggestion, Error, ErrorReportedPromise},
     interpreter::lang::{Expr, ExprKind, Stmt, StmtKind, VarName},
-    interpreter::parser::{expr::expression, parser::Parser, token::Token},
+    interpreter::parser::{expr::expression, lexer::KEYWORDS, parser::Parser, token::Token},
     source::{Located, Span},
 };
 
-pub(super) fn statement<'file>(parser: &mut Parser<'file>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+pub(super) fn statement<'file>(parser: &mut Parser<'file>) -> Result<Stmt, ErrorReportedPromise> {
     let tok = parser.peek();
     match tok.1 {
         Token::OBrace => {
@@ -60,15 +60,15 @@ pub(super) fn statement<'file>(parser: &mut Parser<'file>) -> Result<Stmt<'file>
                 let rhs = expression(parser)?;
                 let semi_sp = parser.consume(|tok| match tok.1 {
                     Token::Semicolon => Ok(tok.0),
-                    _ => Err(Error::new(Some(tok.0), "expected ';' after assignment statement".to_string()).report()),
+                    _ => Err(Error::new(Some(tok.0), "expected ';' after assignment statement".to_string()).with_code(&catalog::MISSING_SEMICOLON)),
                 })?;
-                let total_span = expr.span + semi_sp;
-                make_assignment(expr, rhs, total_span)
+                let total_span = expr.span.clone() + semi_sp;
+                make_assignment(expr, rhs, total_span).map_err(|err| parser.report(err))
             } else {
-                let expr_span = expr.span;
+                let expr_span = expr.span.clone();
                 let semi_sp = parser.consume(|tok| match tok.1 {
                     Token::Semicolon => Ok(tok.0),
-                    _ => Err(Error::new(Some(tok.0), "expected ';' after expression statement".to_string()).report()),
+                    _ => Err(suggest_keyword(&expr, Error::new(Some(tok.0), "expected ';' after expression statement".to_string()).with_code(&catalog::MISSING_SEMICOLON))),
                 })?;
                 Ok(Stmt { kind: StmtKind::Expr(expr), span: expr_span + semi_sp })
             }
@@ -76,7 +76,7 @@ pub(super) fn statement<'file>(parser: &mut Parser<'file>) -> Result<Stmt<'file>
     }
 }
 
-fn finish_block<'file>(parser: &mut Parser<'file>, obrace_tok: Located<'file, Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn finish_block<'file>(parser: &mut Parser<'file>, obrace_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     let mut statements = Vec::new();
 
     while !parser.peek_matches(|tok| matches!(tok, Token::CBrace | Token::Eof)) {
@@ -85,18 +85,18 @@ fn finish_block<'file>(parser: &mut Parser<'file>, obrace_tok: Located<'file, To
 
     let cbrace_sp = parser.consume(|tok| match tok.1 {
         Token::CBrace => Ok(tok.0),
-        _ => Err(Error::new(Some(tok.0), "expected '}' to close block".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected '}' to close block".to_string()).with_code(&catalog::UNCLOSED_DELIMITER).with_label(obrace_tok.0.clone(), "block starts here".to_string())),
     })?;
 
     Ok(Stmt { kind: StmtKind::Block(statements), span: obrace_tok.0 + cbrace_sp })
 }
 
-fn if_statement<'file>(parser: &mut Parser<'file>, if_tok: Located<'file, Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn if_statement<'file>(parser: &mut Parser<'file>, if_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     let cond = expression(parser)?;
 
     let obrace = parser.consume(|tok| match tok.1 {
         Token::OBrace => Ok(tok),
-        _ => Err(Error::new(Some(tok.0), "expected '{' after condition of 'if' statement".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected '{' after condition of 'if' statement".to_string()).with_code(&catalog::EXPECTED_BLOCK)),
     })?;
 
     let true_branch = finish_block(parser, obrace)?;
@@ -110,7 +110,7 @@ fn if_statement<'file>(parser: &mut Parser<'file>, if_tok: Located<'file, Token>
         } else {
             let obrace = parser.consume(|tok| match tok.1 {
                 Token::OBrace => Ok(tok),
-                _ => Err(Error::new(Some(tok.0), "expected either 'if' or '{' after 'else'".to_string()).report()),
+                _ => Err(Error::new(Some(tok.0), "expected either 'if' or '{' after 'else'".to_string()).with_code(&catalog::EXPECTED_BLOCK)),
             })?;
 
             Some(finish_block(parser, obrace)?)
@@ -119,117 +119,128 @@ fn if_statement<'file>(parser: &mut Parser<'file>, if_tok: Located<'file, Token>
         None
     };
 
-    let total_span = if_tok.0 + false_branch.as_ref().map(|branch| branch.span).unwrap_or(true_branch.span);
+    let total_span = if_tok.0.clone() + false_branch.as_ref().map(|branch| branch.span.clone()).unwrap_or_else(|| true_branch.span.clone());
 
     Ok(Stmt { kind: StmtKind::If(if_tok.0, cond, Box::new(true_branch), false_branch.map(Box::new)), span: total_span })
 }
 
-fn for_statement<'file>(parser: &mut Parser<'file>, for_tok: Located<Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn for_statement<'file>(parser: &mut Parser<'file>, for_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     // TODO: decide about these
     todo!()
 }
 
-fn while_statement<'file>(parser: &mut Parser<'file>, while_tok: Located<'file, Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn while_statement<'file>(parser: &mut Parser<'file>, while_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     let cond = expression(parser)?;
 
     let obrace = parser.consume(|tok| match tok.1 {
         Token::OBrace => Ok(tok),
-        _ => Err(Error::new(Some(tok.0), "expected '{' after condition of 'while' loop".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected '{' after condition of 'while' loop".to_string()).with_code(&catalog::EXPECTED_BLOCK)),
     })?;
 
     let body = finish_block(parser, obrace)?;
 
-    let total_span = while_tok.0 + body.span;
+    let total_span = while_tok.0.clone() + body.span.clone();
 
     Ok(Stmt { kind: StmtKind::While(while_tok.0, cond, Box::new(body)), span: total_span })
 }
 
-fn break_statement<'file>(parser: &mut Parser<'file>, break_tok: Located<Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn break_statement<'file>(parser: &mut Parser<'file>, break_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     todo!()
 }
 
-fn continue_statement<'file>(parser: &mut Parser<'file>, continue_tok: Located<Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn continue_statement<'file>(parser: &mut Parser<'file>, continue_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     todo!()
 }
 
-fn var_statement<'file>(parser: &mut Parser<'file>, var_tok: Located<'file, Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn var_statement<'file>(parser: &mut Parser<'file>, var_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     let name = parser.consume(|tok| match tok.1 {
         Token::Identifier(name) => Ok(name),
-        _ => Err(Error::new(Some(tok.0), "expected variable name after 'var'".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected variable name after 'var'".to_string()).with_code(&catalog::EXPECTED_VARIABLE_NAME)),
     })?;
 
     let rhs = if let Some(()) = parser.maybe_consume(|tok| matches!(tok.1, Token::Equal).then_some(())) { Some(expression(parser)?) } else { None };
 
     let semi_sp = parser.consume(|tok| match tok.1 {
         Token::Semicolon => Ok(tok.0),
-        _ => Err(Error::new(Some(tok.0), "expected ';' after 'var' statement".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected ';' after 'var' statement".to_string()).with_code(&catalog::MISSING_SEMICOLON)),
     })?;
 
     Ok(Stmt { kind: StmtKind::MakeVar(VarName(name), rhs), span: var_tok.0 + semi_sp })
 }
 
-fn return_statement<'file>(parser: &mut Parser<'file>, return_tok: Located<'file, Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn return_statement<'file>(parser: &mut Parser<'file>, return_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     let expr = expression(parser)?;
 
     let semi_sp = parser.consume(|tok| match tok.1 {
         Token::Semicolon => Ok(tok.0),
-        _ => Err(Error::new(Some(tok.0), "expected ';' after 'return' statement".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected ';' after 'return' statement".to_string()).with_code(&catalog::MISSING_SEMICOLON)),
     })?;
 
     Ok(Stmt { kind: StmtKind::Return(expr), span: return_tok.0 + semi_sp })
 }
 
-fn print_statement<'file>(parser: &mut Parser<'file>, print_tok: Located<'file, Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn print_statement<'file>(parser: &mut Parser<'file>, print_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     let expr = expression(parser)?;
 
     let semi_sp = parser.consume(|tok| match tok.1 {
         Token::Semicolon => Ok(tok.0),
-        _ => Err(Error::new(Some(tok.0), "expected ';' after 'print' statement".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected ';' after 'print' statement".to_string()).with_code(&catalog::MISSING_SEMICOLON)),
     })?;
 
     Ok(Stmt { kind: StmtKind::Print(expr), span: print_tok.0 + semi_sp })
 }
 
-fn assign_statement<'file>(parser: &mut Parser<'file>, assign_tok: Located<'file, Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn assign_statement<'file>(parser: &mut Parser<'file>, assign_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     let value = expression(parser)?;
 
     parser.consume(|tok| match tok.1 {
         Token::To => Ok(()),
-        _ => Err(Error::new(Some(tok.0), "expected 'to'".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected 'to'".to_string()).with_code(&catalog::EXPECTED_KEYWORD)),
     })?;
 
     let target = expression(parser)?;
 
     let semi_sp = parser.consume(|tok| match tok.1 {
         Token::Semicolon => Ok(tok.0),
-        _ => Err(Error::new(Some(tok.0), "expected ';' after 'assign' statement".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected ';' after 'assign' statement".to_string()).with_code(&catalog::MISSING_SEMICOLON)),
     })?;
 
-    make_assignment(target, value, assign_tok.0 + semi_sp)
+    make_assignment(target, value, assign_tok.0 + semi_sp).map_err(|err| parser.report(err))
 }
 
-fn make_var_statement<'file>(parser: &mut Parser<'file>, make_tok: Located<'file, Token>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn make_var_statement<'file>(parser: &mut Parser<'file>, make_tok: Located<Token>) -> Result<Stmt, ErrorReportedPromise> {
     parser.consume(|tok| match tok.1 {
         Token::Var => Ok(()),
-        _ => Err(Error::new(Some(tok.0), "expected 'var' after 'make'".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected 'var' after 'make'".to_string()).with_code(&catalog::EXPECTED_KEYWORD)),
     })?;
 
     let name = parser.consume(|tok| match tok.1 {
         Token::Identifier(name) => Ok(name),
-        _ => Err(Error::new(Some(tok.0), "expected variable name after 'var'".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected variable name after 'var'".to_string()).with_code(&catalog::EXPECTED_VARIABLE_NAME)),
     })?;
 
     let semi_sp = parser.consume(|tok| match tok.1 {
         Token::Semicolon => Ok(tok.0),
-        _ => Err(Error::new(Some(tok.0), "expected ';' after 'make var' statement".to_string()).report()),
+        _ => Err(Error::new(Some(tok.0), "expected ';' after 'make var' statement".to_string()).with_code(&catalog::MISSING_SEMICOLON)),
     })?;
 
     Ok(Stmt { kind: StmtKind::MakeVar(VarName(name), None), span: make_tok.0 + semi_sp })
 }
 
-fn make_assignment<'file>(target: Expr<'file>, value: Expr<'file>, span: Span<'file>) -> Result<Stmt<'file>, ErrorReportedPromise> {
+fn make_assignment(target: Expr, value: Expr, span: Span) -> Result<Stmt, Error> {
     match target.kind {
-        ExprKind::Var(vn) => Ok(Stmt { kind: StmtKind::AssignVar(vn, value), span }),
-        _ => Err(Error::new(Some(target.span), "invalid assignment target".to_string()).report()),
+        ExprKind::Var(vn) => Ok(Stmt { kind: StmtKind::AssignVar(Located(target.span, vn), value), span }),
+        _ => Err(Error::new(Some(target.span), "invalid assignment target".to_string()).with_code(&catalog::INVALID_ASSIGNMENT_TARGET)),
+    }
+}
+
+// a misspelled keyword like in 'whlie x < 3 { ... }' gets parsed as a variable at the start of an expression statement, so the error only appears once the ';' is missing
+fn suggest_keyword(expr: &Expr, error: Error) -> Error {
+    match &expr.kind {
+        ExprKind::Var(VarName(name)) => match suggestion::closest(name, KEYWORDS.iter().map(|(keyword, _)| *keyword)) {
+            Some(keyword) => error.with_suggestion(format!("did you mean '{keyword}'?"), expr.span.clone(), keyword.to_string()),
+            None => error,
+        },
+        _ => error,
     }
 }
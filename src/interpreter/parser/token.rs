@@ -1,7 +1,7 @@
 use num_bigint::BigInt;
 
-#[derive(Clone)]
-pub(super) enum Token {
+#[derive(Clone, Debug)]
+pub(crate) enum Token {
     OParen,
     CParen,
     OBrack,
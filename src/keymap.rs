@@ -0,0 +1,158 @@
+use crate::app::keyboard::{Key, KeyChord, Modifiers};
+
+const LOCAL_STORAGE_KEY: &str = "pel-keymap";
+
+// everything that can be done with a keyboard shortcut
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Action {
+    Step,
+    Back,
+    Continue,
+    Play,
+    Restart,
+    ZoomIn,
+    ZoomOut,
+    Help,
+}
+
+impl Action {
+    pub(crate) const ALL: [Action; 8] = [Action::Step, Action::Back, Action::Continue, Action::Play, Action::Restart, Action::ZoomIn, Action::ZoomOut, Action::Help];
+
+    // also used to save the keymap, so these should not be changed
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Action::Step => "step",
+            Action::Back => "back",
+            Action::Continue => "continue",
+            Action::Play => "play",
+            Action::Restart => "restart",
+            Action::ZoomIn => "zoom in",
+            Action::ZoomOut => "zoom out",
+            Action::Help => "help",
+        }
+    }
+
+    fn default_chords(self) -> Vec<KeyChord> {
+        let ctrl = Modifiers { ctrl: true, ..Modifiers::default() };
+        match self {
+            Action::Step => vec![KeyChord::plain(Key::Space), KeyChord::plain(Key::Right)],
+            Action::Back => vec![KeyChord::plain(Key::Left), KeyChord::plain(Key::Backspace)],
+            Action::Continue => vec![KeyChord::plain(Key::Character('c'))],
+            Action::Play => vec![KeyChord::plain(Key::Character('p'))],
+            Action::Restart => vec![KeyChord::plain(Key::Character('r'))],
+            Action::ZoomIn => vec![KeyChord::plain(Key::Character('+')), KeyChord::new(ctrl, Key::Character('='))],
+            Action::ZoomOut => vec![KeyChord::plain(Key::Character('-')), KeyChord::new(ctrl, Key::Character('-'))],
+            Action::Help => vec![KeyChord::plain(Key::Character('?'))],
+        }
+    }
+}
+
+// which key chords do which actions, where an action can have any number of chords but a chord only does one action
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct Keymap {
+    bindings: Vec<(Action, Vec<KeyChord>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap { bindings: Action::ALL.into_iter().map(|action| (action, action.default_chords())).collect() }
+    }
+}
+
+impl Keymap {
+    pub(crate) fn action(&self, chord: KeyChord) -> Option<Action> {
+        self.bindings.iter().find(|(_, chords)| chords.contains(&chord)).map(|(action, _)| *action)
+    }
+
+    pub(crate) fn chords(&self, action: Action) -> &[KeyChord] {
+        self.bindings.iter().find(|(bound, _)| *bound == action).map(|(_, chords)| &chords[..]).unwrap_or_default()
+    }
+
+    // replaces all of the chords of the action with this one, taking it away from the action that it was bound to before
+    pub(crate) fn rebind(&mut self, action: Action, chord: KeyChord) {
+        for (bound, chords) in &mut self.bindings {
+            if *bound == action {
+                *chords = vec![chord];
+            } else {
+                chords.retain(|other| *other != chord);
+            }
+        }
+    }
+
+    // one line for every action, like "step: Space Right"
+    fn serialize(&self) -> String {
+        self.bindings.iter().map(|(action, chords)| format!("{}: {}\n", action.name(), chords.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "))).collect()
+    }
+
+    // actions that are missing or cannot be read keep their default chords so that a keymap saved by an older version still works
+    fn deserialize(saved: &str) -> Keymap {
+        let mut keymap = Keymap::default();
+        for line in saved.lines() {
+            let Some((name, chords)) = line.split_once(": ") else { continue };
+            let Some(action) = Action::ALL.into_iter().find(|action| action.name() == name) else { continue };
+            // chords are separated by spaces because the space key is written as "Space", where a comma could be a key
+            let Ok(chords) = chords.split_whitespace().map(str::parse).collect::<Result<Vec<KeyChord>, _>>() else { continue };
+            for (bound, bound_chords) in &mut keymap.bindings {
+                if *bound == action {
+                    bound_chords.clone_from(&chords);
+                } else {
+                    bound_chords.retain(|chord| !chords.contains(chord));
+                }
+            }
+        }
+        keymap
+    }
+
+    pub(crate) fn load() -> Keymap {
+        match web_sys::window().expect("no global window").local_storage() {
+            Ok(Some(storage)) => match storage.get_item(LOCAL_STORAGE_KEY) {
+                Ok(Some(saved)) => Keymap::deserialize(&saved),
+                _ => Keymap::default(),
+            },
+            _ => Keymap::default(),
+        }
+    }
+
+    pub(crate) fn save(&self) {
+        // the same as saving the program, not being able to save only means that the keymap goes back to the default when the page is reopened
+        if let Ok(Some(storage)) = web_sys::window().expect("no global window").local_storage() {
+            let _ = storage.set_item(LOCAL_STORAGE_KEY, &self.serialize());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        app::keyboard::{Key, KeyChord},
+        keymap::{Action, Keymap},
+    };
+
+    #[test]
+    fn rebind() {
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.action(KeyChord::plain(Key::Space)), Some(Action::Step));
+
+        keymap.rebind(Action::Continue, KeyChord::plain(Key::Space));
+        assert_eq!(keymap.action(KeyChord::plain(Key::Space)), Some(Action::Continue));
+        assert_eq!(keymap.action(KeyChord::plain(Key::Character('c'))), None);
+        assert_eq!(keymap.chords(Action::Step), [KeyChord::plain(Key::Right)]);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let mut keymap = Keymap::default();
+        keymap.rebind(Action::Back, KeyChord::plain(Key::Character(',')));
+        keymap.rebind(Action::Help, KeyChord::plain(Key::Right));
+        keymap.rebind(Action::Step, KeyChord::plain(Key::Character(':')));
+        assert_eq!(Keymap::deserialize(&keymap.serialize()), keymap);
+    }
+
+    #[test]
+    fn load_bad_keymap() {
+        let keymap = Keymap::deserialize("step: Hyper+x\nno such action: a\nrestart: Space\nthis line has no colon");
+        assert_eq!(keymap.chords(Action::Step), [KeyChord::plain(Key::Right)]);
+        assert_eq!(keymap.chords(Action::Restart), [KeyChord::plain(Key::Space)]);
+        assert_eq!(keymap.chords(Action::Continue), Keymap::default().chords(Action::Continue));
+    }
+}
@@ -0,0 +1,77 @@
+use std::rc::Rc;
+
+use base64::Engine;
+
+use crate::source::File;
+
+const FRAGMENT_PREFIX: &str = "#code=";
+const LOCAL_STORAGE_NAME_KEY: &str = "pel-last-program-name";
+const LOCAL_STORAGE_SOURCE_KEY: &str = "pel-last-program-source";
+// inflating a link should never be able to use up all of the memory, no matter what is in it
+const MAX_DECOMPRESSED_SIZE: usize = 1 << 20;
+
+// the program that is shown when the page is opened, which is (in order of priority):
+// - the program in the '#code=' fragment of the url, which is how programs are shared
+// - the program that was open last time, which is saved in local storage
+// - the default example program
+pub(crate) fn initial_program() -> Rc<File> {
+    let window = web_sys::window().expect("no global window");
+
+    if let Ok(hash) = window.location().hash() {
+        if hash.starts_with(FRAGMENT_PREFIX) {
+            match decode_fragment(&hash) {
+                Some(source) => return File::new("shared program".to_string(), source),
+                None => web_sys::console::warn_1(&"could not decode program from url, falling back to the last program".into()),
+            }
+        }
+    }
+
+    if let Ok(Some(storage)) = window.local_storage() {
+        if let (Ok(Some(name)), Ok(Some(source))) = (storage.get_item(LOCAL_STORAGE_NAME_KEY), storage.get_item(LOCAL_STORAGE_SOURCE_KEY)) {
+            return File::new(name, source);
+        }
+    }
+
+    File::new("fizzbuzz.pel".to_string(), include_str!("../examples/fizzbuzz.pel").to_string())
+}
+
+pub(crate) fn save_to_local_storage(file: &File) {
+    // not being able to save is not a big deal because it only means the program will not be there when the page is reopened
+    if let Ok(Some(storage)) = web_sys::window().expect("no global window").local_storage() {
+        let _ = storage.set_item(LOCAL_STORAGE_NAME_KEY, &file.name);
+        let _ = storage.set_item(LOCAL_STORAGE_SOURCE_KEY, &file.source);
+    }
+}
+
+// the source is compressed before being encoded so that links to longer programs do not get too long
+pub(crate) fn encode_fragment(source: &str) -> String {
+    let compressed = miniz_oxide::deflate::compress_to_vec(source.as_bytes(), 9);
+    format!("{FRAGMENT_PREFIX}{}", base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(compressed))
+}
+
+fn decode_fragment(fragment: &str) -> Option<String> {
+    let compressed = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(fragment.strip_prefix(FRAGMENT_PREFIX)?).ok()?;
+    let decompressed = miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_DECOMPRESSED_SIZE).ok()?;
+    String::from_utf8(decompressed).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::loader::{decode_fragment, encode_fragment};
+
+    #[test]
+    fn fragment_round_trip() {
+        let source = include_str!("../examples/fizzbuzz.pel");
+        let fragment = encode_fragment(source);
+        assert!(fragment.starts_with("#code="));
+        assert!(fragment.len() < source.len(), "fragment should be compressed");
+        assert_eq!(decode_fragment(&fragment).as_deref(), Some(source));
+    }
+
+    #[test]
+    fn bad_fragment() {
+        assert_eq!(decode_fragment("#code=not valid base64!"), None);
+        assert_eq!(decode_fragment("#code=aGVsbG8"), None); // valid base64 but not compressed
+        assert_eq!(decode_fragment("#other=abc"), None);
+    }
+}
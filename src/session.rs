@@ -0,0 +1,425 @@
+use std::{ops::Range, rc::Rc};
+
+use crate::{
+    app::{
+        keyboard::{Key, KeyChord},
+        theme::{self, Theme},
+        widgets::{
+            aria::Aria,
+            clickable::{Clickable, MouseButton},
+            code_view::code_view,
+            either::Either,
+            error_view::ErrorView,
+            file_picker::FilePicker,
+            flex,
+            focus::Focus,
+            inline_style::InlineStyle,
+            link::Link,
+            padding::Padding,
+            responds_to_keyboard::RespondsToKeyboard,
+            text_area::TextArea,
+            Widget,
+        },
+    },
+    error::{Error, Footnote},
+    interpreter::{
+        error_screen::{error_highlights, ErrorScreen},
+        interpreter::{new_interpreter, Interpreter},
+        parser,
+    },
+    keymap::{Action, Keymap},
+    loader,
+    source::File,
+};
+
+// how often a step is stored for going back to earlier steps, where lower uses more memory but makes going back faster
+const SNAPSHOT_INTERVAL: usize = 100;
+// programs are stopped after this many steps so that a program that never finishes does not use up all of the memory of the page by keeping its steps
+const MAX_STEPS: usize = 1_000_000;
+
+// the program that is currently open, which can be replaced by opening another one
+pub(crate) struct Session {
+    file: Rc<File>,
+    program: Program,
+    // Some while the source code is being edited
+    editor: Option<Editor>,
+    // kept when another program is opened
+    settings: Settings,
+    // Some while the keyboard shortcuts are shown
+    help: Option<Help>,
+}
+enum Program {
+    // boxed because the interpreter is much bigger than the error screen
+    Running(Box<Interpreter>),
+    Errors(ErrorScreen),
+}
+struct Settings {
+    theme: Theme,
+    // in percent
+    zoom: u32,
+    keymap: Keymap,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Settings { theme: Theme::LIGHT, zoom: 100, keymap: Keymap::default() }
+    }
+}
+struct Help {
+    // the action that the next key chord is bound to
+    rebinding: Option<Action>,
+}
+struct Editor {
+    // the errors in the source code as it is now, which are found again after every change
+    errors: Vec<Error>,
+    // whether the restarted program should go forward to the step that the old one was at
+    keep_step: bool,
+}
+
+impl Program {
+    fn new(file: &Rc<File>) -> Program {
+        match parser::parse_statements(file) {
+            Ok(stmts) => Program::Running(Box::new(new_interpreter(Rc::clone(file), stmts, Some(MAX_STEPS), SNAPSHOT_INTERVAL))),
+            Err(errors) => Program::Errors(ErrorScreen::new(Rc::clone(file), errors)),
+        }
+    }
+}
+
+impl Session {
+    pub(crate) fn open(file: Rc<File>) -> Session {
+        loader::save_to_local_storage(&file);
+        Session { program: Program::new(&file), file, editor: None, settings: Settings { keymap: Keymap::load(), ..Settings::default() }, help: None }
+    }
+
+    fn open_other(&mut self, file: Rc<File>) {
+        let settings = std::mem::take(&mut self.settings);
+        *self = Session { settings, ..Session::open(file) };
+    }
+
+    fn zoom(&mut self, change: i32) {
+        self.settings.zoom = self.settings.zoom.saturating_add_signed(change).clamp(theme::MIN_ZOOM, theme::MAX_ZOOM);
+    }
+
+    // returns whether the chord did something
+    fn key_pressed(&mut self, chord: KeyChord) -> bool {
+        let escape = chord == KeyChord::plain(Key::Escape);
+        match &mut self.help {
+            // escape cancels rebinding, so it cannot be bound to anything
+            Some(help) if help.rebinding.is_some() => {
+                let action = help.rebinding.take().expect("rebinding was just checked to be Some");
+                if !escape {
+                    self.settings.keymap.rebind(action, chord);
+                    self.settings.keymap.save();
+                }
+                true
+            }
+            Some(_) if escape => {
+                self.help = None;
+                true
+            }
+            _ => self.settings.keymap.action(chord).is_some_and(|action| self.run_action(action)),
+        }
+    }
+
+    // returns false if the action cannot be done now, like stepping while editing
+    fn run_action(&mut self, action: Action) -> bool {
+        let interpreter = match (&self.editor, &mut self.program) {
+            (None, Program::Running(interpreter)) => Some(interpreter),
+            _ => None,
+        };
+        match (action, interpreter) {
+            (Action::Step, Some(interpreter)) => interpreter.step_forward(),
+            (Action::Back, Some(interpreter)) => interpreter.step_back(),
+            (Action::Continue, Some(interpreter)) => interpreter.continue_to_breakpoint(),
+            (Action::Play, Some(interpreter)) => interpreter.toggle_playing(),
+            (Action::Restart, Some(interpreter)) => interpreter.restart(),
+            (Action::Step | Action::Back | Action::Continue | Action::Play | Action::Restart, None) => return false,
+            (Action::ZoomIn, _) => self.zoom(theme::ZOOM_STEP as i32),
+            (Action::ZoomOut, _) => self.zoom(-(theme::ZOOM_STEP as i32)),
+            (Action::Help, _) => self.toggle_help(),
+        }
+        true
+    }
+
+    fn toggle_help(&mut self) {
+        self.help = match self.help {
+            Some(_) => None,
+            None => Some(Help { rebinding: None }),
+        };
+    }
+
+    fn reset_keymap(&mut self) {
+        self.settings.keymap = Keymap::default();
+        self.settings.keymap.save();
+    }
+
+    fn start_editing(&mut self) {
+        self.editor = Some(Editor { errors: parse_errors(&self.file), keep_step: true });
+    }
+
+    // the program is not restarted until editing is finished so that it does not run all of the half typed versions of the program
+    fn edit(&mut self, source: String) {
+        self.file = File::new(self.file.name.clone(), source);
+        loader::save_to_local_storage(&self.file);
+        if let Some(editor) = &mut self.editor {
+            editor.errors = parse_errors(&self.file);
+        }
+    }
+
+    fn apply_fix(&mut self, range: Range<usize>, replacement: &str) {
+        let mut source = self.file.source.clone();
+        source.replace_range(range, replacement);
+        self.edit(source);
+    }
+
+    fn finish_editing(&mut self) {
+        let Some(editor) = self.editor.take() else {
+            return;
+        };
+        let step = match &self.program {
+            Program::Running(interpreter) if editor.keep_step => Some(interpreter.position()),
+            _ => None,
+        };
+        self.program = Program::new(&self.file);
+        if let (Program::Running(interpreter), Some(step)) = (&mut self.program, step) {
+            interpreter.go_to(step);
+        }
+    }
+
+    pub(crate) fn view(&self) -> impl Widget<Session> {
+        let theme = &self.settings.theme.zoomed(self.settings.zoom);
+        let program_view = match (&self.editor, &self.program) {
+            (Some(editor), _) => Either::new_left(self.view_editor(editor, theme)),
+            (None, Program::Running(interpreter)) => Either::new_right(Either::new_left(Focus::new(
+                |session: &mut Session| match &mut session.program {
+                    Program::Running(interpreter) => Some(&mut **interpreter),
+                    Program::Errors(_) => None,
+                },
+                interpreter.view(theme),
+            ))),
+            (None, Program::Errors(error_screen)) => Either::new_right(Either::new_right(Focus::new(
+                |session: &mut Session| match &mut session.program {
+                    Program::Errors(error_screen) => Some(error_screen),
+                    Program::Running(_) => None,
+                },
+                error_screen.view(theme),
+            ))),
+        };
+
+        let edit_button = if self.editor.is_some() {
+            Clickable::new(MouseButton::Main, Session::finish_editing as fn(&mut Session), Padding::all_around(theme.label("run".to_string()), 5.0))
+        } else {
+            Clickable::new(MouseButton::Main, Session::start_editing as fn(&mut Session), Padding::all_around(theme.label("edit".to_string()), 5.0))
+        };
+
+        let themes = flex::homogeneous::Flex::new(
+            flex::Direction::Horizontal,
+            Theme::ALL
+                .into_iter()
+                .map(|choice| {
+                    let text = if choice == self.settings.theme { format!("[{}]", choice.name) } else { choice.name.to_string() };
+                    (flex::ItemSettings::Fixed, Clickable::new(MouseButton::Main, move |session: &mut Session| session.settings.theme = choice, Padding::all_around(theme.label(text), 5.0)))
+                })
+                .collect(),
+        );
+        let zoom = flex!(horizontal {
+            smaller: (
+                flex::ItemSettings::Fixed,
+                Aria::label(
+                    "smaller text".to_string(),
+                    Clickable::new(MouseButton::Main, |session: &mut Session| session.zoom(-(theme::ZOOM_STEP as i32)), Padding::all_around(theme.label("A-".to_string()), 5.0))
+                )
+            ),
+            amount: (flex::ItemSettings::Fixed, Padding::all_around(theme.label(format!("{}%", self.settings.zoom)), 5.0)),
+            bigger: (
+                flex::ItemSettings::Fixed,
+                Aria::label(
+                    "bigger text".to_string(),
+                    Clickable::new(MouseButton::Main, |session: &mut Session| session.zoom(theme::ZOOM_STEP as i32), Padding::all_around(theme.label("A+".to_string()), 5.0))
+                )
+            ),
+        });
+
+        let page = flex!(vertical {
+            toolbar: (
+                flex::ItemSettings::Fixed,
+                Padding::all_around(
+                    Aria::new(
+                        "toolbar",
+                        "program".to_string(),
+                        flex!(horizontal {
+                            name: (flex::ItemSettings::Flex(1.0), theme.label(self.file.name.clone())),
+                            themes: (flex::ItemSettings::Fixed, themes),
+                            zoom: (flex::ItemSettings::Fixed, zoom),
+                            help: (
+                                flex::ItemSettings::Fixed,
+                                Clickable::new(MouseButton::Main, Session::toggle_help as fn(&mut Session), Padding::all_around(theme.label("keyboard shortcuts".to_string()), 5.0))
+                            ),
+                            edit: (flex::ItemSettings::Fixed, edit_button),
+                            open: (
+                                flex::ItemSettings::Fixed,
+                                Aria::label("open a program".to_string(), FilePicker::new(".pel,.txt", |session: &mut Session, name, source| session.open_other(File::new(name, source))))
+                            ),
+                            share: (flex::ItemSettings::Fixed, Link::new("link to this program".to_string(), loader::encode_fragment(&self.file.source))),
+                        })
+                    ),
+                    5.0
+                )
+            ),
+            help: (
+                flex::ItemSettings::Fixed,
+                match &self.help {
+                    Some(help) => Either::new_left(self.view_help(help, theme)),
+                    None => Either::new_right(theme.label(String::new())),
+                }
+            ),
+            program: (flex::ItemSettings::Flex(1.0), program_view),
+        });
+        // the listener is on the whole page so that shortcuts work wherever the focus is, except for text boxes and sliders which keep the keys for themselves
+        InlineStyle::new(theme.root_css(), RespondsToKeyboard::new(Session::key_pressed, page))
+    }
+
+    // shown on top of everything else
+    fn view_help(&self, help: &Help, theme: &Theme) -> impl Widget<Session> {
+        let bindings = flex::homogeneous::Flex::new_vertical(
+            Action::ALL
+                .into_iter()
+                .map(|action| {
+                    let chords = match self.settings.keymap.chords(action) {
+                        [] => "not bound".to_string(),
+                        chords => chords.iter().map(ToString::to_string).collect::<Vec<_>>().join(" or "),
+                    };
+                    let chords = if help.rebinding == Some(action) { "press the new keys (escape to cancel)".to_string() } else { chords };
+                    (
+                        flex::ItemSettings::Fixed,
+                        flex!(horizontal {
+                            action: (flex::ItemSettings::Flex(1.0), Padding::all_around(theme.label(action.name().to_string()), 5.0)),
+                            chords: (flex::ItemSettings::Flex(1.0), Padding::all_around(theme.code_label(chords), 5.0)),
+                            change: (
+                                flex::ItemSettings::Fixed,
+                                Clickable::new(
+                                    MouseButton::Main,
+                                    move |session: &mut Session| {
+                                        if let Some(help) = &mut session.help {
+                                            help.rebinding = Some(action);
+                                        }
+                                    },
+                                    Padding::all_around(theme.label("change".to_string()), 5.0)
+                                )
+                            ),
+                        }),
+                    )
+                })
+                .collect(),
+        );
+
+        let overlay = flex!(vertical {
+            header: (
+                flex::ItemSettings::Fixed,
+                flex!(horizontal {
+                    title: (flex::ItemSettings::Flex(1.0), Padding::all_around(theme.label("keyboard shortcuts".to_string()), 5.0)),
+                    reset: (
+                        flex::ItemSettings::Fixed,
+                        Clickable::new(MouseButton::Main, Session::reset_keymap as fn(&mut Session), Padding::all_around(theme.label("reset to defaults".to_string()), 5.0))
+                    ),
+                    close: (flex::ItemSettings::Fixed, Clickable::new(MouseButton::Main, Session::toggle_help as fn(&mut Session), Padding::all_around(theme.label("close".to_string()), 5.0))),
+                })
+            ),
+            bindings: (flex::ItemSettings::Fixed, bindings),
+        });
+        InlineStyle::new(
+            format!(
+                "position: fixed; top: 10vh; left: 20vw; right: 20vw; max-height: 80vh; overflow-y: auto; z-index: 1; padding: 10px; background-color: {}; border: 1px solid {};",
+                theme.background.to_css_color(),
+                theme.border.to_css_color()
+            ),
+            Aria::new("dialog", "keyboard shortcuts".to_string(), overlay),
+        )
+    }
+
+    fn view_editor(&self, editor: &Editor, theme: &Theme) -> impl Widget<Session> {
+        let header = match editor.errors.len() {
+            0 => "no errors".to_string(),
+            1 => "1 error".to_string(),
+            count => format!("{count} errors"),
+        };
+        let errors = flex::homogeneous::Flex::new_vertical(
+            editor
+                .errors
+                .iter()
+                .map(|error| {
+                    // errors that know how they can be fixed get a button for each fix
+                    let fixes = error
+                        .footnotes
+                        .iter()
+                        .filter_map(|footnote| match footnote {
+                            Footnote::Help { message, replacement: Some((span, replacement)) } => {
+                                let (range, replacement) = (span.start..span.end, replacement.clone());
+                                Some((
+                                    flex::ItemSettings::Fixed,
+                                    Clickable::new(
+                                        MouseButton::Main,
+                                        move |session: &mut Session| session.apply_fix(range.clone(), &replacement),
+                                        Padding::all_around(theme.label(format!("fix: {message}")), 5.0),
+                                    ),
+                                ))
+                            }
+                            Footnote::Help { replacement: None, .. } | Footnote::Note(_) => None,
+                        })
+                        .collect();
+                    (
+                        flex::ItemSettings::Fixed,
+                        flex!(vertical {
+                            error: (flex::ItemSettings::Fixed, Padding::all_around(ErrorView::new(error, theme), 5.0)),
+                            fixes: (flex::ItemSettings::Fixed, flex::homogeneous::Flex::new_vertical(fixes)),
+                        }),
+                    )
+                })
+                .collect(),
+        );
+        // the errors are also marked in the code under the text area, because a text area cannot highlight parts of its text
+        let (primary_highlight, secondary_highlights) = error_highlights(&self.file, &editor.errors, theme);
+        let keep_step = if editor.keep_step { "[go back to the same step after running]" } else { "go back to the same step after running" };
+
+        flex!(horizontal {
+            source: (
+                flex::ItemSettings::Flex(0.5),
+                flex!(vertical {
+                    text_area: (
+                        flex::ItemSettings::Flex(0.6),
+                        Padding::all_around(Aria::label("source code".to_string(), TextArea::new(self.file.source.clone(), |session: &mut Session, source| session.edit(source))), 5.0)
+                    ),
+                    marked: (
+                        flex::ItemSettings::Flex(0.4),
+                        Padding::all_around(Aria::new("region", "source code with the errors marked".to_string(), code_view(primary_highlight, secondary_highlights, Vec::new(), theme)), 5.0)
+                    ),
+                })
+            ),
+            errors: (
+                flex::ItemSettings::Flex(0.5),
+                Padding::all_around(
+                    flex!(vertical {
+                        keep_step: (
+                            flex::ItemSettings::Fixed,
+                            Clickable::new(
+                                MouseButton::Main,
+                                |session: &mut Session| {
+                                    if let Some(editor) = &mut session.editor {
+                                        editor.keep_step = !editor.keep_step;
+                                    }
+                                },
+                                Padding::all_around(theme.label(keep_step.to_string()), 5.0)
+                            )
+                        ),
+                        header: (flex::ItemSettings::Fixed, theme.label(header)),
+                        list: (flex::ItemSettings::Flex(1.0), Aria::new("region", "errors".to_string(), errors)),
+                    }),
+                    5.0
+                )
+            ),
+        })
+    }
+}
+
+fn parse_errors(file: &Rc<File>) -> Vec<Error> {
+    parser::parse_statements(file).err().unwrap_or_default()
+}
@@ -1,6 +1,7 @@
 use std::{
-    fmt::Display,
+    fmt::{Debug, Display},
     ops::{Add, Range},
+    rc::Rc,
 };
 
 use line_span::LineSpanExt;
@@ -14,64 +15,79 @@ pub(crate) struct File {
 
 impl File {
     // TODO: have this be private and have load() instead?
-    pub(crate) fn new(name: String, source: String) -> Self {
+    pub(crate) fn new(name: String, source: String) -> Rc<Self> {
         let lines = source.line_spans().map(|line_span| (line_span.range(), line_span.as_str().to_string())).collect();
-        Self { name, source, lines, _dont_construct: () }
+        Rc::new(Self { name, source, lines, _dont_construct: () })
     }
 
-    pub(crate) fn eof_span(&self) -> Span<'_> {
-        Span { file: self, start: self.source.len(), end: self.source.len(), _dont_construct: () }
+    pub(crate) fn eof_span(self: &Rc<Self>) -> Span {
+        Span { file: Rc::clone(self), start: self.source.len(), end: self.source.len(), _dont_construct: () }
     }
 }
 
-#[derive(Copy, Clone)]
-pub(crate) struct Location<'file> {
-    file: &'file File,
+#[derive(Clone)]
+pub(crate) struct Location {
+    file: Rc<File>,
     index: usize,
 }
 
-#[derive(Copy, Clone)]
-pub(crate) struct Span<'file> {
-    pub(crate) file: &'file File,
+// files are reference counted so that spans (and everything that contains them) do not borrow from the file, which lets the program be swapped out while the app is running
+#[derive(Clone)]
+pub(crate) struct Span {
+    pub(crate) file: Rc<File>,
     pub(crate) start: usize,
     pub(crate) end: usize,
 
     _dont_construct: (),
 }
-impl Span<'_> {
-    pub(crate) fn new_from_start_and_end(file: &File, start: usize, end: usize) -> Span<'_> {
+impl Span {
+    pub(crate) fn new_from_start_and_end(file: &Rc<File>, start: usize, end: usize) -> Span {
         assert!(start <= end, "cannot have span that ends earlier than it starts");
-        Span { file, start, end, _dont_construct: () }
+        Span { file: Rc::clone(file), start, end, _dont_construct: () }
+    }
+
+    pub(crate) fn start_line_col(&self) -> (usize, usize) {
+        get_line_col(&self.file, self.start)
+    }
+    pub(crate) fn end_line_col(&self) -> (usize, usize) {
+        get_line_col(&self.file, self.end)
     }
 }
 
-impl<'a> Add for Span<'a> {
-    type Output = Span<'a>;
+impl Add for Span {
+    type Output = Span;
 
     fn add(self, rhs: Self) -> Self::Output {
-        assert!(std::ptr::eq(self.file, rhs.file), "cannot join two spans from different files");
+        assert!(Rc::ptr_eq(&self.file, &rhs.file), "cannot join two spans from different files");
         Span { file: self.file, start: std::cmp::min(self.start, rhs.start), end: std::cmp::max(self.end, rhs.end), _dont_construct: () }
     }
 }
 
-#[derive(Copy, Clone)]
-pub(crate) struct Located<'file, T>(pub(crate) Span<'file>, pub(crate) T);
+#[derive(Clone, Debug)]
+pub(crate) struct Located<T>(pub(crate) Span, pub(crate) T);
+
+// only the range is shown because the file would make debug output of syntax trees unreadably long
+impl Debug for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
 
-impl Display for Location<'_> {
+impl Display for Location {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (line, col) = get_line_col(self.file, self.index);
+        let (line, col) = get_line_col(&self.file, self.index);
         write!(f, "{}:{}:{}", self.file.name, line, col)
     }
 }
-impl Display for Span<'_> {
+impl Display for Span {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (start_line, start_col) = get_line_col(self.file, self.start);
-        let (end_line, end_col) = get_line_col(self.file, self.end);
+        let (start_line, start_col) = self.start_line_col();
+        let (end_line, end_col) = self.end_line_col();
 
         if start_line == end_line {
             if start_col == end_col {
                 // zero length span
-                write!(f, "{}:{}:{}", self.file.name, start_line, start_line)
+                write!(f, "{}:{}:{}", self.file.name, start_line, start_col)
             } else {
                 // span contained entirely within one line
                 write!(f, "{}:{}:{}-{}", self.file.name, start_line, start_col, end_col)
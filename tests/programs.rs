@@ -0,0 +1,93 @@
+// runs every program in tests/programs and compares what it does against the files next to it:
+//     <name>.out      what the program prints
+//     <name>.err      the exit code and the errors, if the program has any
+//     <name>.trace    every step that the interpreter takes, in the format that 'pel trace' prints
+// a missing file means that nothing is expected
+// the options in <name>.args, if there is one, are passed to both 'pel run' and 'pel trace'
+// run with PEL_BLESS=1 to regenerate the expected files from what the programs currently do
+//
+// 'return' statements are not implemented yet, so there are no programs for them
+// there is no program for '--max-time' because where it stops depends on how fast the machine is
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const BLESS_VAR: &str = "PEL_BLESS";
+
+struct Outcome {
+    out: String,
+    err: String,
+    trace: String,
+}
+
+fn run_pel(subcommand: &str, program: &Path, args: &[String]) -> std::process::Output {
+    // run from the root of the crate with a relative path so that the paths in the errors are the same on every machine
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    Command::new(env!("CARGO_BIN_EXE_pel"))
+        .current_dir(manifest_dir)
+        .arg(subcommand)
+        .arg(program.strip_prefix(manifest_dir).expect("program should be inside of the crate"))
+        .args(args)
+        .output()
+        .expect("could not run pel")
+}
+
+fn run_program(program: &Path) -> Outcome {
+    let args: Vec<String> = fs::read_to_string(program.with_extension("args")).unwrap_or_default().split_whitespace().map(str::to_string).collect();
+    let run = run_pel("run", program, &args);
+    let trace = run_pel("trace", program, &args);
+
+    let err =
+        if run.status.success() { String::new() } else { format!("exit code {}\n\n{}", run.status.code().map_or("none".to_string(), |code| code.to_string()), String::from_utf8_lossy(&run.stderr)) };
+    Outcome { out: String::from_utf8_lossy(&run.stdout).into_owned(), err, trace: String::from_utf8_lossy(&trace.stdout).into_owned() }
+}
+
+fn programs() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("programs");
+    let mut programs: Vec<PathBuf> = fs::read_dir(&dir)
+        .expect("could not read tests/programs")
+        .map(|entry| entry.expect("could not read directory entry").path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "pel"))
+        .collect();
+    programs.sort();
+    programs
+}
+
+// returns a description of the mismatch if there is one
+fn check_file(path: &Path, actual: &str, bless: bool) -> Option<String> {
+    if bless {
+        if actual.is_empty() {
+            let _ = fs::remove_file(path);
+        } else {
+            fs::write(path, actual).expect("could not write expected file");
+        }
+        return None;
+    }
+
+    let expected = fs::read_to_string(path).unwrap_or_default();
+    if expected == actual {
+        None
+    } else {
+        Some(format!("{} does not match\n--- expected\n{expected}\n--- actual\n{actual}", path.display()))
+    }
+}
+
+#[test]
+fn programs_match_expected() {
+    let bless = std::env::var_os(BLESS_VAR).is_some();
+    let programs = programs();
+    assert!(!programs.is_empty(), "no programs found in tests/programs");
+
+    let mut failures = Vec::new();
+    for program in &programs {
+        let outcome = run_program(program);
+        for (extension, actual) in [("out", &outcome.out), ("err", &outcome.err), ("trace", &outcome.trace)] {
+            failures.extend(check_file(&program.with_extension(extension), actual, bless));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}\n\n{} mismatch(es), run with {BLESS_VAR}=1 to accept the new output", failures.join("\n\n"), failures.len());
+}